@@ -15,6 +15,7 @@ struct VertexOutput {
 struct Input {
     time: f32;
     seed: f32;
+    resolution: vec2<f32>;
 };
 
 [[group(0), binding(0)]]
@@ -62,7 +63,7 @@ fn sm_vr(st: vec2<f32>, time: f32) -> f32 {
 
 [[stage(fragment)]]
 fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
-    let coords = in.uv.xy * 20.0;
+    let coords = (in.uv.xy - vec2<f32>(0.5, 0.5)) * input.resolution / 64.0;
 
     var c = 0.0;
     for (var i = 3.0; i >= 0.0; i = i - 1.0)   {
@@ -17,7 +17,11 @@ struct Input {
     time: f32;
     seed: f32;
     sickness: f32;
-    dilatation: f32;
+    flow_speed: f32;
+    healthy: vec4<f32>;
+    zones: array<vec4<f32>, 8>;
+    vessels: array<vec4<f32>, 16>;
+    vessel_widths: array<vec4<f32>, 16>;
 };
 
 [[group(0), binding(0)]]
@@ -65,16 +69,26 @@ fn sm_vr(st: vec2<f32>, time: f32) -> f32 {
 
 [[stage(fragment)]]
 fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
-    let on_screen = in.uv.xy * 2.0 - vec2<f32>(1.0, 1.0);
-    let coords = on_screen * input.resolution ;
-    let abs_coords = abs(coords);
-
-    let transparency = 1.0;
-
-    let alpha1 = smoothStep(0.0, transparency, abs_coords.x / input.dilatation);
-    let alpha2 = smoothStep(0.0, transparency, abs_coords.y / input.dilatation);
+    let world = vec2<f32>(in.uv.x - 0.5, 0.5 - in.uv.y) * input.resolution;
+
+    var across = 2.0;
+    var along = 0.0;
+    for (var i = 0; i < 16; i = i + 1) {
+        let segment = input.vessels[i];
+        let width = input.vessel_widths[i];
+        if (width.x > 0.0) {
+            let direction = segment.zw - segment.xy;
+            let t = clamp(dot(world - segment.xy, direction) / dot(direction, direction), 0.0, 1.0);
+            let d = distance(world, segment.xy + direction * t) / width.x;
+            if (d < across) {
+                across = d;
+                along = width.y + t * length(direction);
+            }
+        }
+    }
+    let vessel = 1.0 - smoothStep(0.5, 1.0, across);
 
-    let v_coords = in.uv.xy * 100.0;
+    let v_coords = world / 12.0 * input.healthy.w;
     var c = 0.0;
     for (var i = 3.0; i >= 0.0; i = i - 1.0)   {
         var vr = sm_vr(v_coords * pow(2.0, i), input.time * 5.0 + input.seed );
@@ -85,11 +99,24 @@ fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
     }
 
     let sick = vec3<f32>(0.3, 0.5, 0.01);
-    let healthy = vec3<f32>(1.0, 0.08, 0.52);
+    let healthy = input.healthy.xyz;
     let color = smoothStep(healthy, sick, vec3<f32>(input.sickness)) * (c);
 
+    var infection = 0.0;
+    for (var i = 0; i < 8; i = i + 1) {
+        let zone = input.zones[i];
+        if (zone.z > 0.0) {
+            infection = max(infection, 1.0 - smoothStep(zone.z * 0.7, zone.z, distance(world, zone.xy)));
+        }
+    }
+    let fungal = vec3<f32>(0.55, 0.42, 0.12) * (c + 0.3);
+
+    let streaks = 0.5 + 0.5 * sin((along - input.time * input.flow_speed) / 25.0 + sin(across * 6.0) * 2.0);
+    let current = (1.0 - smoothStep(0.6, 1.0, across)) * streaks * 0.35;
+    let plasma = vec3<f32>(0.9, 0.25, 0.2);
+
     return vec4<f32>(
-        color,
-        clamp(smoothStep(0.0, 1.0, (alpha2 + alpha1) / 2.0), 0.0, 1.0)
+        mix(mix(color, plasma, current), fungal, infection * 0.8),
+        max(max(1.0 - vessel, current), infection * 0.8)
     );
 }
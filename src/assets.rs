@@ -36,6 +36,10 @@ pub struct ProgressAssets {
     pub free_healthcare: Handle<Image>,
     #[asset(path = "sprites/pr-parental-leave.png")]
     pub parental_leave: Handle<Image>,
+    #[asset(path = "sprites/pr-phage-therapy.png")]
+    pub phage_therapy: Handle<Image>,
+    #[asset(path = "sprites/pr-booster-shots.png")]
+    pub booster_shots: Handle<Image>,
 }
 
 #[derive(AssetCollection)]
@@ -48,8 +52,36 @@ pub struct InGameAssets {
     pub virus: Handle<Image>,
     #[asset(path = "sprites/cancer.png")]
     pub cancer: Handle<Image>,
+    #[asset(path = "sprites/toxic-bacteria.png")]
+    pub toxic_bacteria: Handle<Image>,
+    #[asset(path = "sprites/toxin-cloud.png")]
+    pub toxin_cloud: Handle<Image>,
+    #[asset(path = "sprites/fungus.png")]
+    pub fungus: Handle<Image>,
+    #[asset(path = "sprites/entry-point.png")]
+    pub entry_point: Handle<Image>,
+    #[asset(path = "sprites/parasite.png")]
+    pub parasite: Handle<Image>,
     #[asset(path = "sprites/white-cell.png")]
     pub white_cell: Handle<Image>,
+    #[asset(path = "sprites/macrophage.png")]
+    pub macrophage: Handle<Image>,
+    #[asset(path = "sprites/killer-t.png")]
+    pub killer_t: Handle<Image>,
+    #[asset(path = "sprites/b-cell.png")]
+    pub b_cell: Handle<Image>,
+    #[asset(path = "sprites/antibody.png")]
+    pub antibody: Handle<Image>,
+    #[asset(path = "sprites/fever-wave.png")]
+    pub fever_wave: Handle<Image>,
+    #[asset(path = "sprites/lymph-node.png")]
+    pub lymph_node: Handle<Image>,
+    #[asset(path = "sprites/bone-marrow.png")]
+    pub bone_marrow: Handle<Image>,
+    #[asset(path = "sprites/vessel-exit.png")]
+    pub vessel_exit: Handle<Image>,
+    #[asset(path = "sprites/capsule.png")]
+    pub capsule: Handle<Image>,
 }
 
 #[derive(AssetCollection)]
@@ -18,7 +18,10 @@ use bevy::{
 };
 use rand::Rng;
 
-use crate::{game::z_layers, GameState};
+use crate::{
+    game::{arena, z_layers},
+    GameState,
+};
 
 #[derive(Component)]
 struct ScreenTag;
@@ -46,8 +49,8 @@ fn setup(
     if !*done {
         debug!("Loading Bloodfield");
 
-        let window = windows.get_primary().unwrap();
-        let resolution = Vec2::new(window.width(), window.height());
+        let (width, height) = arena::size(windows.get_primary().unwrap());
+        let resolution = Vec2::new(width, height);
 
         commands
             .spawn_bundle(MaterialMesh2dBundle {
@@ -60,6 +63,7 @@ fn setup(
                 material: materials.add(BloodfieldMaterial {
                     time: 0.0,
                     seed: rand::thread_rng().gen::<i16>() as f32,
+                    resolution,
                 }),
                 ..Default::default()
             })
@@ -83,6 +87,7 @@ fn update_bloodfield_material(
 struct BloodfieldMaterial {
     time: f32,
     seed: f32,
+    resolution: Vec2,
 }
 
 #[derive(Clone)]
@@ -102,7 +107,12 @@ impl RenderAsset for BloodfieldMaterial {
         extracted_asset: Self::ExtractedAsset,
         (render_device, material_pipeline): &mut SystemParamItem<Self::Param>,
     ) -> Result<Self::PreparedAsset, PrepareAssetError<Self::ExtractedAsset>> {
-        let value = Vec4::new(extracted_asset.time, extracted_asset.seed, 0.0, 0.0);
+        let value = Vec4::new(
+            extracted_asset.time,
+            extracted_asset.seed,
+            extracted_asset.resolution.x,
+            extracted_asset.resolution.y,
+        );
         let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
             contents: value.as_std140().as_bytes(),
             label: Some("Bloodfield Settings Buffer"),
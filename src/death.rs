@@ -61,30 +61,39 @@ fn death(
                 ui.add_space(10.0);
                 ui.label(text);
                 ui.add_space(30.0);
+                ui.label("Which trait should the next generation inherit?");
+                ui.add_space(10.0);
                 ui.vertical_centered_justified(|ui| {
                     ui.set_max_width(350.0);
-                    button(
-                        ui,
-                        "Try Again...",
-                        || {
-                            audio.play(
-                                audio_assets.button.clone_weak(),
-                                PlaybackSettings {
-                                    repeat: false,
-                                    speed: 1.0,
-                                    volume: 0.2,
-                                },
-                            );
+                    for gene in global_state.genome.clone().genes() {
+                        button(
+                            ui,
+                            format!("Try Again with {}...", gene),
+                            || {
+                                audio.play(
+                                    audio_assets.button.clone_weak(),
+                                    PlaybackSettings {
+                                        repeat: false,
+                                        speed: 1.0,
+                                        volume: 0.2,
+                                    },
+                                );
 
-                            global_state.generation += 1;
-                            global_state.progress += host_state.age;
-                            global_state.expectancy = host_state.age.max(global_state.expectancy);
-                            let _ = state.set(GameState::Menu);
-                        },
-                        true,
-                        false,
-                    );
-                    ui.add_space(10.0);
+                                global_state.generation += 1;
+                                global_state.progress += host_state.age;
+                                global_state.expectancy =
+                                    host_state.age.max(global_state.expectancy);
+                                global_state.resistance.evolve(&host_state.exposure);
+                                global_state.memory = host_state.memory.inherited();
+                                global_state.genome = global_state.genome.offspring(gene);
+                                let _ = state.set(GameState::Menu);
+                            },
+                            true,
+                            false,
+                        );
+                        ui.small(gene.details());
+                        ui.add_space(10.0);
+                    }
                 });
             });
         });
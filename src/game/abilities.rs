@@ -0,0 +1,249 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use strum::{EnumIter, IntoEnumIterator};
+
+use crate::assets::{AudioAssets, InGameAssets};
+
+use super::{
+    antibodies::spawn_antibody, immune_system::ImmuneSystem, pathogens::Pathogen, z_layers,
+    HostState, ScreenTag,
+};
+
+const FEVER_RADIUS: f32 = 120.0;
+const FEVER_DAMAGE: f32 = 60.0;
+const FEVER_COST: f32 = 0.1;
+const DASH_DURATION: f32 = 0.3;
+const DASH_BOOST: f32 = 3.0;
+const DASH_INFLAMMATION: f32 = 0.05;
+const BURST_ANTIBODIES: usize = 12;
+
+#[derive(EnumIter, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Ability {
+    FeverPulse,
+    InflammationDash,
+    AntibodyBurst,
+}
+
+impl fmt::Display for Ability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ability::FeverPulse => write!(f, "Q Fever"),
+            Ability::InflammationDash => write!(f, "E Dash"),
+            Ability::AntibodyBurst => write!(f, "R Antibodies"),
+        }
+    }
+}
+
+impl Ability {
+    const fn cooldown(self) -> f32 {
+        match self {
+            Ability::FeverPulse => 8.0,
+            Ability::InflammationDash => 4.0,
+            Ability::AntibodyBurst => 10.0,
+        }
+    }
+
+    const fn key(self) -> KeyCode {
+        match self {
+            Ability::FeverPulse => KeyCode::Q,
+            Ability::InflammationDash => KeyCode::E,
+            Ability::AntibodyBurst => KeyCode::R,
+        }
+    }
+
+    const fn button(self) -> GamepadButtonType {
+        match self {
+            Ability::FeverPulse => GamepadButtonType::West,
+            Ability::InflammationDash => GamepadButtonType::South,
+            Ability::AntibodyBurst => GamepadButtonType::North,
+        }
+    }
+}
+
+pub struct Activated(Ability);
+
+pub struct Abilities {
+    cooldowns: Vec<(Ability, Timer)>,
+    dash: Timer,
+}
+
+impl Default for Abilities {
+    fn default() -> Self {
+        let mut dash = Timer::from_seconds(DASH_DURATION, false);
+        dash.set_elapsed(dash.duration());
+        Abilities {
+            cooldowns: Ability::iter()
+                .map(|ability| {
+                    let mut cooldown = Timer::from_seconds(ability.cooldown(), false);
+                    cooldown.set_elapsed(cooldown.duration());
+                    (ability, cooldown)
+                })
+                .collect(),
+            dash,
+        }
+    }
+}
+
+impl Abilities {
+    pub fn ready(&self) -> impl Iterator<Item = (Ability, f32)> + '_ {
+        self.cooldowns
+            .iter()
+            .map(|(ability, cooldown)| (*ability, cooldown.percent()))
+    }
+
+    pub fn speed_boost(&self) -> f32 {
+        if self.dash.percent() < 1.0 {
+            DASH_BOOST
+        } else {
+            1.0
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct FeverWave {
+    lifetime: Timer,
+}
+
+pub fn trigger(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    button_inputs: Res<Input<GamepadButton>>,
+    time: Res<Time>,
+    mut abilities: ResMut<Abilities>,
+    mut activated: EventWriter<Activated>,
+) {
+    abilities.dash.tick(time.delta());
+    for (ability, cooldown) in abilities.cooldowns.iter_mut() {
+        if !cooldown.tick(time.delta()).finished() {
+            continue;
+        }
+        let pressed = keyboard_input.just_pressed(ability.key())
+            || gamepads.iter().any(|gamepad| {
+                button_inputs.just_pressed(GamepadButton(*gamepad, ability.button()))
+            });
+        if pressed {
+            cooldown.reset();
+            activated.send(Activated(*ability));
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn fever_pulse(
+    mut commands: Commands,
+    mut activated: EventReader<Activated>,
+    mut immune_system: Query<(&RigidBodyPositionComponent, &mut ImmuneSystem)>,
+    mut pathogens: Query<(&RigidBodyPositionComponent, &mut Pathogen)>,
+    host_state: Res<HostState>,
+    assets: Res<InGameAssets>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+) {
+    for _ in activated
+        .iter()
+        .filter(|Activated(ability)| *ability == Ability::FeverPulse)
+    {
+        let (rb_position, mut immune_system) = immune_system.single_mut();
+        let position: Vec2 = rb_position.position.translation.into();
+        immune_system.health -= immune_system.original_health * FEVER_COST;
+        for (rb_position, mut pathogen) in pathogens.iter_mut() {
+            let target: Vec2 = rb_position.position.translation.into();
+            if target.distance_squared(position) < FEVER_RADIUS.powi(2) {
+                let damage = host_state.memory.damage(pathogen.kind, FEVER_DAMAGE);
+                pathogen.hit(damage);
+            }
+        }
+        commands
+            .spawn_bundle(SpriteBundle {
+                transform: Transform::from_translation(position.extend(z_layers::TOXIN)),
+                sprite: Sprite {
+                    color: Color::rgba(1.0, 0.5, 0.1, 0.6),
+                    custom_size: Some(Vec2::splat(16.0)),
+                    ..Default::default()
+                },
+                texture: assets.fever_wave.clone_weak(),
+                ..Default::default()
+            })
+            .insert(FeverWave {
+                lifetime: Timer::from_seconds(0.4, false),
+            })
+            .insert(ScreenTag);
+        audio.play(
+            audio_assets.pathogen_spawn.clone_weak(),
+            PlaybackSettings {
+                repeat: false,
+                volume: 0.3,
+                speed: 0.5,
+            },
+        );
+    }
+}
+
+pub fn fever_waves(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut waves: Query<(Entity, &mut Sprite, &mut FeverWave)>,
+) {
+    for (entity, mut sprite, mut wave) in waves.iter_mut() {
+        if wave.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            sprite.custom_size = Some(Vec2::splat(
+                16.0 + (FEVER_RADIUS * 2.0 - 16.0) * wave.lifetime.percent(),
+            ));
+            sprite.color.set_a(0.6 * wave.lifetime.percent_left());
+        }
+    }
+}
+
+pub fn inflammation_dash(
+    mut activated: EventReader<Activated>,
+    mut abilities: ResMut<Abilities>,
+    mut host_state: ResMut<HostState>,
+) {
+    for _ in activated
+        .iter()
+        .filter(|Activated(ability)| *ability == Ability::InflammationDash)
+    {
+        abilities.dash.reset();
+        host_state.inflammation = (host_state.inflammation + DASH_INFLAMMATION).min(1.0);
+    }
+}
+
+pub fn antibody_burst(
+    mut commands: Commands,
+    mut activated: EventReader<Activated>,
+    immune_system: Query<(&RigidBodyPositionComponent, &ImmuneSystem)>,
+    assets: Res<InGameAssets>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+) {
+    for _ in activated
+        .iter()
+        .filter(|Activated(ability)| *ability == Ability::AntibodyBurst)
+    {
+        let (rb_position, immune_system) = immune_system.single();
+        let position: Vec2 = rb_position.position.translation.into();
+        for i in 0..BURST_ANTIBODIES {
+            let angle = i as f32 / BURST_ANTIBODIES as f32 * std::f32::consts::TAU;
+            spawn_antibody(
+                &mut commands,
+                position,
+                Vec2::new(angle.cos(), angle.sin()),
+                immune_system.attack_damage,
+                assets.antibody.clone_weak(),
+            );
+        }
+        audio.play(
+            audio_assets.button.clone_weak(),
+            PlaybackSettings {
+                repeat: false,
+                volume: 0.2,
+                speed: 1.5,
+            },
+        );
+    }
+}
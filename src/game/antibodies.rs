@@ -0,0 +1,198 @@
+use bevy::prelude::*;
+use bevy_egui::EguiContext;
+use bevy_rapier2d::prelude::*;
+
+use crate::assets::InGameAssets;
+
+use super::{
+    immune_system::ImmuneSystem, pathogens::Pathogen, white_cells::WhiteCell, z_layers, HostState,
+    ScreenTag,
+};
+
+const ANTIBODY_SPEED: f32 = 250.0;
+const ANTIBODY_RANGE: f32 = 250.0;
+const AIMED_FIRE_RATE: f32 = 3.0;
+
+#[derive(Component)]
+pub struct Antibody {
+    damage: f32,
+    lifetime: Timer,
+}
+
+#[derive(Component)]
+pub struct AntibodyProducer {
+    pub reload: Timer,
+}
+
+pub fn spawn_antibody(
+    commands: &mut Commands,
+    position: Vec2,
+    direction: Vec2,
+    damage: f32,
+    texture: Handle<Image>,
+) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            transform: Transform::from_translation(position.extend(z_layers::IMMUNE_SYSTEM)),
+            texture,
+            ..Default::default()
+        })
+        .insert_bundle(RigidBodyBundle {
+            position: position.into(),
+            mass_properties: RigidBodyMassPropsFlags::ROTATION_LOCKED.into(),
+            velocity: RigidBodyVelocity {
+                linvel: (direction.normalize_or_zero() * ANTIBODY_SPEED).into(),
+                angvel: 0.0,
+            }
+            .into(),
+            ..Default::default()
+        })
+        .insert_bundle(ColliderBundle {
+            collider_type: ColliderType::Sensor.into(),
+            shape: ColliderShape::ball(2.0).into(),
+            flags: ActiveEvents::INTERSECTION_EVENTS.into(),
+            ..Default::default()
+        })
+        .insert(RigidBodyPositionSync::Discrete)
+        .insert(Antibody {
+            damage,
+            lifetime: Timer::from_seconds(ANTIBODY_RANGE / ANTIBODY_SPEED, false),
+        })
+        .insert(ScreenTag);
+}
+
+pub fn produce(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut producers: Query<(&Transform, &WhiteCell, &mut AntibodyProducer)>,
+    pathogens: Query<&Transform, With<Pathogen>>,
+    assets: Res<InGameAssets>,
+) {
+    for (transform, white_cell, mut producer) in producers.iter_mut() {
+        if !producer.reload.tick(time.delta()).just_finished() {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        if let Some(target) = pathogens
+            .iter()
+            .map(|transform| transform.translation.truncate())
+            .filter(|target| target.distance_squared(position) < ANTIBODY_RANGE.powi(2))
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .partial_cmp(&b.distance_squared(position))
+                    .unwrap()
+            })
+        {
+            spawn_antibody(
+                &mut commands,
+                position,
+                target - position,
+                white_cell.damage / 2.0,
+                assets.antibody.clone_weak(),
+            );
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn aim(
+    mut commands: Commands,
+    time: Res<Time>,
+    windows: Res<Windows>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut egui_context: ResMut<EguiContext>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    camera: Query<&Transform, With<Camera>>,
+    immune_system: Query<(&RigidBodyPositionComponent, &ImmuneSystem)>,
+    assets: Res<InGameAssets>,
+    mut reload: Local<f32>,
+) {
+    *reload = (*reload - time.delta_seconds()).max(0.0);
+    let (rb_position, immune_system) = immune_system.single();
+    let position: Vec2 = rb_position.position.translation.into();
+
+    let mut aim = None;
+    for gamepad in gamepads.iter().cloned() {
+        let right_stick = Vec2::new(
+            axes.get(GamepadAxis(gamepad, GamepadAxisType::RightStickX))
+                .unwrap(),
+            axes.get(GamepadAxis(gamepad, GamepadAxisType::RightStickY))
+                .unwrap(),
+        );
+        if right_stick.length() > 0.2 {
+            aim = Some(right_stick);
+        }
+    }
+    // clicks on the HUD panels aren't aimed at anything
+    if aim.is_none()
+        && mouse_input.pressed(MouseButton::Left)
+        && !egui_context.ctx_mut().wants_pointer_input()
+    {
+        let window = windows.get_primary().unwrap();
+        if let Some(cursor) = window.cursor_position() {
+            let camera = camera.single().translation.truncate();
+            let target = cursor - Vec2::new(window.width(), window.height()) / 2.0 + camera;
+            aim = Some(target - position);
+        }
+    }
+
+    if let Some(aim) = aim {
+        if *reload <= 0.0 {
+            *reload = 1.0 / (AIMED_FIRE_RATE * (1.0 + immune_system.attack_spawn_rate));
+            spawn_antibody(
+                &mut commands,
+                position,
+                aim,
+                immune_system.attack_damage / 3.0,
+                assets.antibody.clone_weak(),
+            );
+        }
+    }
+}
+
+pub fn expire(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut antibodies: Query<(Entity, &mut Antibody)>,
+) {
+    for (entity, mut antibody) in antibodies.iter_mut() {
+        if antibody.lifetime.tick(time.delta()).just_finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn hit(
+    mut commands: Commands,
+    mut intersection_events: EventReader<IntersectionEvent>,
+    antibodies: Query<&Antibody>,
+    mut pathogens: Query<&mut Pathogen>,
+    host_state: Res<HostState>,
+) {
+    let mut used = vec![];
+    for event in intersection_events.iter() {
+        if event.intersecting {
+            let e1 = event.collider1.entity();
+            let e2 = event.collider2.entity();
+            let (antibody, pathogen) = if antibodies.get(e1).is_ok() && pathogens.get(e2).is_ok() {
+                (e1, e2)
+            } else if antibodies.get(e2).is_ok() && pathogens.get(e1).is_ok() {
+                (e2, e1)
+            } else {
+                continue;
+            };
+            if used.contains(&antibody) {
+                continue;
+            }
+            let mut pathogen = pathogens.get_mut(pathogen).unwrap();
+            if pathogen.is_dead() {
+                continue;
+            }
+            used.push(antibody);
+            let damage = antibodies.get(antibody).unwrap().damage;
+            pathogen.hit(host_state.memory.damage(pathogen.kind, damage));
+            commands.entity(antibody).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2, Color32, Stroke},
+    EguiContext,
+};
+
+use super::{
+    immune_system::ImmuneSystem, pathogens::Pathogen, structures::Structure, vessel::Vessels,
+    HostState,
+};
+
+pub const ARENA_SCALE: f32 = 2.0;
+const FOLLOW_SPEED: f32 = 4.0;
+const MINIMAP_WIDTH: f32 = 180.0;
+
+pub fn size(window: &Window) -> (f32, f32) {
+    (
+        window.width() * 0.985 * ARENA_SCALE,
+        window.height() * 0.975 * ARENA_SCALE,
+    )
+}
+
+pub fn follow(
+    time: Res<Time>,
+    windows: Res<Windows>,
+    immune_system: Query<&Transform, (With<ImmuneSystem>, Without<Camera>)>,
+    mut camera: Query<&mut Transform, With<Camera>>,
+) {
+    let window = windows.get_primary().unwrap();
+    let (width, height) = size(window);
+    // keep the view inside the arena
+    let bounds = Vec2::new(
+        (width - window.width()).max(0.0) / 2.0,
+        (height - window.height()).max(0.0) / 2.0,
+    );
+    let target = immune_system.single().translation.truncate();
+    let mut transform = camera.single_mut();
+    let position = transform
+        .translation
+        .truncate()
+        .lerp(target, (FOLLOW_SPEED * time.delta_seconds()).min(1.0))
+        .clamp(-bounds, bounds);
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn minimap(
+    mut egui_context: ResMut<EguiContext>,
+    windows: Res<Windows>,
+    host_state: Res<HostState>,
+    vessels: Res<Vessels>,
+    immune_system: Query<&Transform, With<ImmuneSystem>>,
+    pathogens: Query<&Transform, With<Pathogen>>,
+    structures: Query<(&Transform, &Structure)>,
+    camera: Query<&Transform, With<Camera>>,
+) {
+    let window = windows.get_primary().unwrap();
+    let (width, height) = size(window);
+    let scale = MINIMAP_WIDTH / width;
+
+    egui::Area::new("minimap")
+        .anchor(Align2::RIGHT_BOTTOM, [-10.0, -40.0])
+        .interactable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            let (rect, _) = ui.allocate_exact_size(
+                egui::vec2(MINIMAP_WIDTH, height * scale),
+                egui::Sense::hover(),
+            );
+            let to_map =
+                |position: Vec2| rect.center() + egui::vec2(position.x, -position.y) * scale;
+            let painter = ui.painter();
+            painter.rect(
+                rect,
+                2.0,
+                Color32::from_rgba_unmultiplied(40, 0, 0, 180),
+                Stroke::new(1.0, Color32::DARK_GRAY),
+            );
+            for segment in vessels.segments.iter() {
+                painter.line_segment(
+                    [to_map(segment.start), to_map(segment.end)],
+                    Stroke::new(
+                        segment.width * host_state.dilatation / 5.0 * 2.0 * scale,
+                        Color32::from_rgba_unmultiplied(160, 20, 20, 120),
+                    ),
+                );
+            }
+
+            let camera = camera.single().translation.truncate();
+            let view = Vec2::new(window.width(), window.height()) / 2.0;
+            painter.rect_stroke(
+                egui::Rect::from_two_pos(to_map(camera - view), to_map(camera + view)),
+                0.0,
+                Stroke::new(1.0, Color32::GRAY),
+            );
+
+            for (transform, structure) in structures.iter() {
+                let color = if structure.active {
+                    Color32::LIGHT_BLUE
+                } else if structure.destroyed {
+                    Color32::DARK_RED
+                } else {
+                    Color32::GRAY
+                };
+                painter.circle_filled(to_map(transform.translation.truncate()), 3.0, color);
+            }
+            for transform in pathogens.iter() {
+                painter.circle_filled(
+                    to_map(transform.translation.truncate()),
+                    1.5,
+                    Color32::YELLOW,
+                );
+            }
+            painter.circle_filled(
+                to_map(immune_system.single().translation.truncate()),
+                3.0,
+                Color32::WHITE,
+            );
+        });
+}
@@ -0,0 +1,191 @@
+use std::{f32::consts::PI, fmt};
+
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2, Color32, RichText},
+    EguiContext,
+};
+use rand::{seq::SliceRandom, Rng};
+
+use crate::assets::AudioAssets;
+
+use super::{
+    immune_system::ImmuneSystem,
+    pathogens::{Pathogen, PathogenKind},
+    HostState,
+};
+
+const CALM_RATE: f32 = 0.4;
+const CROWDED: f32 = 80.0;
+const ANNOUNCEMENT_DURATION: f32 = 3.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outbreak {
+    FluSeason,
+    FoodPoisoning,
+    Pandemic,
+}
+
+impl fmt::Display for Outbreak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outbreak::FluSeason => write!(f, "Flu Season"),
+            Outbreak::FoodPoisoning => write!(f, "Food Poisoning"),
+            Outbreak::Pandemic => write!(f, "Pandemic"),
+        }
+    }
+}
+
+impl Outbreak {
+    fn duration(self) -> f32 {
+        match self {
+            Outbreak::FluSeason => 30.0,
+            Outbreak::FoodPoisoning => 15.0,
+            Outbreak::Pandemic => 45.0,
+        }
+    }
+
+    fn mix(self, kind: PathogenKind) -> f32 {
+        match (self, kind) {
+            (Outbreak::FluSeason, PathogenKind::Virus) => 3.0,
+            (Outbreak::FluSeason, PathogenKind::Bacteria) => 1.0,
+            (Outbreak::FoodPoisoning, PathogenKind::Bacteria) => 4.0,
+            (Outbreak::FoodPoisoning, PathogenKind::Fungus) => 1.0,
+            (Outbreak::Pandemic, PathogenKind::Virus) => 5.0,
+            (Outbreak::Pandemic, PathogenKind::Bacteria) => 1.5,
+            (Outbreak::Pandemic, PathogenKind::Fungus) => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    fn intensity(self, progress: f32) -> f32 {
+        match self {
+            // builds up slowly, peaks mid season then fades
+            Outbreak::FluSeason => (progress * PI).sin(),
+            // hits at once and wears off
+            Outbreak::FoodPoisoning => (1.0 - progress).powi(2),
+            // long plateau at full strength
+            Outbreak::Pandemic => (progress * 4.0).min(1.0) * ((1.0 - progress) * 4.0).min(1.0),
+        }
+    }
+}
+
+enum Phase {
+    Calm(Timer),
+    Wave(Outbreak, Timer),
+}
+
+pub struct Director {
+    phase: Phase,
+    adaptation: f32,
+    announcement: Option<(String, Timer)>,
+}
+
+impl Default for Director {
+    fn default() -> Self {
+        Director {
+            phase: Phase::Calm(Timer::from_seconds(30.0, false)),
+            adaptation: 1.0,
+            announcement: None,
+        }
+    }
+}
+
+impl Director {
+    pub fn announce(&mut self, announcement: String) {
+        self.announcement = Some((
+            announcement,
+            Timer::from_seconds(ANNOUNCEMENT_DURATION, false),
+        ));
+    }
+
+    pub fn multiplier(&self, kind: PathogenKind) -> f32 {
+        match &self.phase {
+            Phase::Calm(_) if kind == PathogenKind::Cancer => 1.0,
+            Phase::Calm(_) => CALM_RATE,
+            Phase::Wave(outbreak, timer) => {
+                1.0 + outbreak.intensity(timer.percent()) * outbreak.mix(kind) * self.adaptation
+            }
+        }
+    }
+}
+
+pub fn direct(
+    mut director: ResMut<Director>,
+    time: Res<Time>,
+    host_state: Res<HostState>,
+    immune_system: Query<&ImmuneSystem>,
+    pathogens: Query<&Pathogen>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+) {
+    let immune_system = immune_system.single();
+    let health = (immune_system.health / immune_system.original_health).clamp(0.0, 1.0);
+    let crowding = (pathogens.iter().len() as f32 / CROWDED).min(1.0);
+    // a healthy host with a clean field gets harsher outbreaks, a struggling one gets mercy
+    let target = (0.5 + health - crowding).clamp(0.25, 1.5);
+    director.adaptation += (target - director.adaptation) * (time.delta_seconds() / 10.0).min(1.0);
+
+    if let Some((_, timer)) = director.announcement.as_mut() {
+        if timer.tick(time.delta()).finished() {
+            director.announcement = None;
+        }
+    }
+
+    let finished = match &mut director.phase {
+        Phase::Calm(timer) | Phase::Wave(_, timer) => timer.tick(time.delta()).finished(),
+    };
+    if !finished {
+        return;
+    }
+    let mut rng = rand::thread_rng();
+    let (phase, announcement) = match director.phase {
+        Phase::Calm(_) => {
+            let outbreaks: &[Outbreak] = if host_state.age > 100.0 {
+                &[
+                    Outbreak::FluSeason,
+                    Outbreak::FoodPoisoning,
+                    Outbreak::Pandemic,
+                ]
+            } else {
+                &[Outbreak::FluSeason, Outbreak::FoodPoisoning]
+            };
+            let outbreak = *outbreaks.choose(&mut rng).unwrap();
+            audio.play(
+                audio_assets.pathogen_spawn.clone_weak(),
+                PlaybackSettings {
+                    repeat: false,
+                    volume: 0.3,
+                    speed: 0.3,
+                },
+            );
+            (
+                Phase::Wave(outbreak, Timer::from_seconds(outbreak.duration(), false)),
+                format!("{} outbreak!", outbreak),
+            )
+        }
+        Phase::Wave(..) => (
+            Phase::Calm(Timer::from_seconds(
+                rng.gen_range(20.0..35.0) / director.adaptation.max(0.5),
+                false,
+            )),
+            "The outbreak subsides".to_string(),
+        ),
+    };
+    director.phase = phase;
+    director.announce(announcement);
+}
+
+pub fn announcements(mut egui_context: ResMut<EguiContext>, director: Res<Director>) {
+    if let Some((announcement, timer)) = director.announcement.as_ref() {
+        let alpha = (timer.percent_left() * 2.0).min(1.0);
+        egui::Area::new("announcement")
+            .anchor(Align2::CENTER_TOP, [0.0, 80.0])
+            .interactable(false)
+            .show(egui_context.ctx_mut(), |ui| {
+                ui.label(RichText::new(announcement).heading().color(
+                    Color32::from_rgba_unmultiplied(255, 255, 255, (alpha * 255.0) as u8),
+                ));
+            });
+    }
+}
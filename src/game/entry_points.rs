@@ -0,0 +1,155 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::assets::InGameAssets;
+
+use super::{
+    arena,
+    interventions::Inventory,
+    pathogens::{spawn_incoming, Incoming, PathogenKind},
+    z_layers, ScreenTag,
+};
+
+const WARNING_DELAY: f32 = 1.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EntryKind {
+    Wound,
+    Airway,
+    GutWall,
+}
+
+impl fmt::Display for EntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryKind::Wound => write!(f, "Wound"),
+            EntryKind::Airway => write!(f, "Airway"),
+            EntryKind::GutWall => write!(f, "Gut Wall"),
+        }
+    }
+}
+
+impl EntryKind {
+    pub fn weight(self, kind: PathogenKind) -> f32 {
+        match (self, kind) {
+            (EntryKind::Wound, PathogenKind::Bacteria) => 3.0,
+            (EntryKind::Wound, PathogenKind::Virus) => 1.0,
+            (EntryKind::Airway, PathogenKind::Bacteria) => 1.0,
+            (EntryKind::Airway, PathogenKind::Virus) => 3.0,
+            (EntryKind::GutWall, PathogenKind::Bacteria) => 3.0,
+            (EntryKind::GutWall, PathogenKind::Virus) => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            EntryKind::Wound => Color::rgba(0.6, 0.05, 0.05, 0.6),
+            EntryKind::Airway => Color::rgba(0.55, 0.75, 0.95, 0.5),
+            EntryKind::GutWall => Color::rgba(0.55, 0.4, 0.2, 0.6),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct EntryPoint {
+    pub kind: EntryKind,
+}
+
+#[derive(Component)]
+pub struct SpawnWarning {
+    incoming: Incoming,
+    timer: Timer,
+}
+
+impl SpawnWarning {
+    pub fn kind(&self) -> PathogenKind {
+        self.incoming.kind
+    }
+}
+
+pub fn setup(mut commands: Commands, windows: Res<Windows>, assets: Res<InGameAssets>) {
+    let window = windows.get_primary().unwrap();
+    let (width, height) = arena::size(window);
+    let wound = rand::thread_rng().gen_range(0.0..std::f32::consts::TAU);
+    for (kind, position) in [
+        (EntryKind::Airway, Vec2::new(-width * 0.35, height * 0.33)),
+        (EntryKind::Airway, Vec2::new(width * 0.35, height * 0.33)),
+        (EntryKind::GutWall, Vec2::new(-width * 0.3, -height * 0.38)),
+        (EntryKind::GutWall, Vec2::new(width * 0.3, -height * 0.38)),
+        (
+            EntryKind::Wound,
+            Vec2::new(wound.cos() * width * 0.42, wound.sin() * height * 0.35),
+        ),
+    ] {
+        debug!("{} opened at {:?}", kind, position);
+        commands
+            .spawn_bundle(SpriteBundle {
+                transform: Transform::from_translation(position.extend(z_layers::ENTRY_POINT)),
+                sprite: Sprite {
+                    color: kind.color(),
+                    custom_size: Some(Vec2::splat(40.0)),
+                    ..Default::default()
+                },
+                texture: assets.entry_point.clone_weak(),
+                ..Default::default()
+            })
+            .insert(EntryPoint { kind })
+            .insert(ScreenTag);
+    }
+}
+
+pub fn telegraph(
+    commands: &mut Commands,
+    incoming: Incoming,
+    position: Vec2,
+    texture: Handle<Image>,
+) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            transform: Transform::from_translation(position.extend(z_layers::SPAWN_WARNING)),
+            sprite: Sprite {
+                color: Color::rgba(1.0, 0.2, 0.1, 0.0),
+                custom_size: Some(Vec2::splat(20.0)),
+                ..Default::default()
+            },
+            texture,
+            ..Default::default()
+        })
+        .insert(SpawnWarning {
+            incoming,
+            timer: Timer::from_seconds(WARNING_DELAY, false),
+        })
+        .insert(ScreenTag);
+}
+
+pub fn warnings(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut warnings: Query<(Entity, &Transform, &mut Sprite, &mut SpawnWarning)>,
+    assets: Res<InGameAssets>,
+    inventory: Res<Inventory>,
+) {
+    // telegraphed arrivals wait until the hospital stay is over
+    if inventory.hospitalised() {
+        return;
+    }
+    for (entity, transform, mut sprite, mut warning) in warnings.iter_mut() {
+        if warning.timer.tick(time.delta()).just_finished() {
+            commands.entity(entity).despawn();
+            spawn_incoming(
+                &mut commands,
+                warning.incoming,
+                transform.translation.truncate(),
+                &assets,
+            );
+        } else {
+            let progress = warning.timer.percent();
+            let pulse = (progress * std::f32::consts::TAU * 3.0).sin() * 0.5 + 0.5;
+            sprite.color.set_a(0.3 + 0.5 * pulse);
+            sprite.custom_size = Some(Vec2::splat(20.0 + 24.0 * progress));
+        }
+    }
+}
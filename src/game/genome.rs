@@ -0,0 +1,125 @@
+use std::fmt;
+
+use rand::{prelude::IteratorRandom, Rng};
+use strum::{EnumIter, IntoEnumIterator};
+
+use crate::progress::Effect;
+
+const GENOME_SIZE: usize = 3;
+const INHERITANCE: f64 = 0.5;
+const MUTATION: f64 = 0.2;
+
+#[derive(EnumIter, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Gene {
+    StrongImmunity,
+    AllergyProne,
+    FastMetabolism,
+    WideVessels,
+    FragileCells,
+    IronStomach,
+}
+
+impl fmt::Display for Gene {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Gene::StrongImmunity => f.pad("Strong Immunity"),
+            Gene::AllergyProne => f.pad("Allergy-Prone"),
+            Gene::FastMetabolism => f.pad("Fast Metabolism"),
+            Gene::WideVessels => f.pad("Wide Vessels"),
+            Gene::FragileCells => f.pad("Fragile Cells"),
+            Gene::IronStomach => f.pad("Iron Stomach"),
+        }
+    }
+}
+
+impl Gene {
+    pub const fn details(self) -> &'static str {
+        match self {
+            Gene::StrongImmunity => "More health and stronger white cells",
+            Gene::AllergyProne => "Stronger white cells, slower healing",
+            Gene::FastMetabolism => "Faster movement and healing, less health",
+            Gene::WideVessels => "Larger blood vessels",
+            Gene::FragileCells => "Higher cancer risk",
+            Gene::IronStomach => "Bacteria risk reduction",
+        }
+    }
+
+    fn apply(self, effect: &mut Effect) {
+        match self {
+            Gene::StrongImmunity => {
+                effect.health += 10.0;
+                effect.attack += 0.2;
+            }
+            Gene::AllergyProne => {
+                effect.attack += 0.3;
+                effect.regen -= 0.1;
+            }
+            Gene::FastMetabolism => {
+                effect.speed += 15.0;
+                effect.regen += 0.2;
+                effect.health -= 5.0;
+            }
+            Gene::WideVessels => {
+                effect.dilatation += 300.0;
+            }
+            Gene::FragileCells => {
+                effect.cancer += 0.1;
+            }
+            Gene::IronStomach => {
+                effect.bacteria -= 0.3;
+            }
+        }
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct Genome(Vec<Gene>);
+
+impl Genome {
+    pub fn random() -> Genome {
+        let mut genome = Genome::default();
+        genome.fill();
+        genome
+    }
+
+    // the chosen gene is always passed on, the others only by chance and may mutate
+    pub fn offspring(&self, passed_on: Gene) -> Genome {
+        let mut rng = rand::thread_rng();
+        let mut child = Genome(vec![passed_on]);
+        for gene in self.0.iter().filter(|gene| **gene != passed_on) {
+            if !rng.gen_bool(INHERITANCE) {
+                continue;
+            }
+            let gene = if rng.gen_bool(MUTATION) {
+                Gene::iter().choose(&mut rng).unwrap()
+            } else {
+                *gene
+            };
+            if !child.0.contains(&gene) {
+                child.0.push(gene);
+            }
+        }
+        child.fill();
+        child
+    }
+
+    fn fill(&mut self) {
+        let mut rng = rand::thread_rng();
+        while self.0.len() < GENOME_SIZE {
+            let gene = Gene::iter().choose(&mut rng).unwrap();
+            if !self.0.contains(&gene) {
+                self.0.push(gene);
+            }
+        }
+    }
+
+    pub fn genes(&self) -> impl Iterator<Item = Gene> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn apply(&self, effect: &mut Effect) {
+        for gene in self.genes() {
+            gene.apply(effect);
+        }
+    }
+}
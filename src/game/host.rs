@@ -1,8 +1,13 @@
 use bevy::prelude::*;
 
-use crate::{assets::AudioAssets, GameState, GlobalState};
+use crate::{assets::AudioAssets, progress::Strains, GameState, GlobalState};
 
-use super::{immune_system::ImmuneSystem, pathogens::Pathogen};
+use super::{
+    immune_system::ImmuneSystem, memory::ImmuneMemory, pathogens::Pathogen, regions::Region,
+    stages::LifeStage,
+};
+
+const INFLAMMATION_THRESHOLD: f32 = 0.7;
 
 pub enum Status {
     Healthy,
@@ -15,6 +20,7 @@ pub struct Risks {
     pub bacteria: f32,
     pub virus: f32,
     pub cancer: f32,
+    pub fungus: f32,
 }
 
 pub struct HostState {
@@ -26,6 +32,26 @@ pub struct HostState {
     pub dilatation: f32,
     pub next_level_up: f32,
     pub exp: usize,
+    pub next_boss: usize,
+    pub exposure: Strains,
+    pub inflammation: f32,
+    pub memory: ImmuneMemory,
+    pub region: Region,
+    pub stage: LifeStage,
+    // drives everything generated for the run, the vessel network and the tissue texture
+    pub seed: u64,
+    // a boss was beaten but its level up couldn't be pushed yet
+    pub pending_boss_reward: bool,
+}
+
+impl HostState {
+    pub fn overreaction(&self) -> f32 {
+        ((self.inflammation - INFLAMMATION_THRESHOLD) / (1.0 - INFLAMMATION_THRESHOLD)).max(0.0)
+    }
+
+    pub fn is_inflamed(&self) -> bool {
+        self.inflammation > INFLAMMATION_THRESHOLD
+    }
 }
 
 pub fn aging(
@@ -52,6 +78,9 @@ pub fn aging(
     if host_state.risks.cancer <= 0.01 && host_state.age > 50.0 {
         host_state.risks.cancer += 0.2;
     }
+    if host_state.risks.fungus <= 0.01 && host_state.age > 80.0 {
+        host_state.risks.fungus += 0.05;
+    }
     if (host_state.age + host_state.exp as f32 * 2.0) > host_state.next_level_up {
         host_state.next_level_up += (host_state.next_level_up * 1.5).min(150.0);
         audio.play(
@@ -83,8 +112,9 @@ pub fn state_update(
         host_state.status = Status::Healthy
     }
     host_state.sickness = (pathogens.iter().len() as f32
-        / ((global_state.generation as f32 + 1.0).min(5.0) * 25.0))
-        .min(1.0);
+        / ((global_state.generation as f32 + 1.0).min(5.0) * 25.0)
+        + host_state.overreaction())
+    .min(1.0);
 
     if immune_system.health <= 0.0 {
         host_state.status = Status::Dead;
@@ -1,16 +1,25 @@
 use bevy::prelude::*;
 use bevy_egui::egui::lerp;
 use bevy_rapier2d::prelude::*;
-use rand::Rng;
-use strum::IntoEnumIterator;
+use rand::{seq::SliceRandom, Rng};
 
-use crate::{
-    assets::InGameAssets,
-    progress::{Effect, Progress},
-    GlobalState,
+use crate::{assets::InGameAssets, progress::Progress, GlobalState};
+
+use super::{
+    abilities::Abilities,
+    antibodies::AntibodyProducer,
+    arena,
+    interventions::Inventory,
+    lifestyle::Lifestyle,
+    pathogens::{Cancer, Fungus, Pathogen},
+    toxins::ToxinCloud,
+    vessel::Vessels,
+    white_cells::{WhiteCell, WhiteCellKind},
+    z_layers, HostState, ScreenTag,
 };
 
-use super::{white_cells::WhiteCell, z_layers, HostState, ScreenTag};
+const FUNGAL_DAMAGE: f32 = 1.0;
+const ANGIOGENESIS_RADIUS: f32 = 150.0;
 
 #[derive(Component)]
 pub struct ImmuneSystem {
@@ -18,6 +27,8 @@ pub struct ImmuneSystem {
     pub health: f32,
     pub original_health: f32,
     pub attack_spawn_rate: f32,
+    pub attack_damage: f32,
+    pub cell_types: Vec<WhiteCellKind>,
 }
 
 impl ImmuneSystem {
@@ -27,6 +38,17 @@ impl ImmuneSystem {
             health,
             original_health: health,
             attack_spawn_rate,
+            attack_damage: 30.0,
+            cell_types: vec![WhiteCellKind::Neutrophil],
+        }
+    }
+
+    pub fn unlock_cell_type(&mut self) {
+        if let Some(kind) = WhiteCellKind::UNLOCK_ORDER
+            .iter()
+            .find(|kind| !self.cell_types.contains(kind))
+        {
+            self.cell_types.push(*kind);
         }
     }
 }
@@ -40,16 +62,22 @@ pub fn setup(mut commands: Commands, global_state: Res<GlobalState>, assets: Res
     let mut attack = global_state.generation as f32 / 180.0
         + global_state.progress / 5000.0
         + (global_state.generation as f32 - 18.0).max(0.0) / 100.0;
-    let mut effect = Effect::default();
-    for progress in Progress::iter() {
-        if global_state.has(&progress) {
-            effect.apply(progress);
-        }
-    }
+    let effect = global_state.effect();
     speed += effect.speed;
     health += effect.health;
     attack += effect.attack;
 
+    let mut immune_system = ImmuneSystem::new(speed, health, attack);
+    for (progress, kind) in [
+        (Progress::FreeHealthcare, WhiteCellKind::Macrophage),
+        (Progress::PreventiveMeasures, WhiteCellKind::KillerT),
+        (Progress::Vaccine, WhiteCellKind::BCell),
+    ] {
+        if global_state.has(&progress) {
+            immune_system.cell_types.push(kind);
+        }
+    }
+
     commands
         .spawn_bundle(SpriteBundle {
             transform: Transform::from_xyz(0.0, 0.0, z_layers::IMMUNE_SYSTEM),
@@ -72,10 +100,11 @@ pub fn setup(mut commands: Commands, global_state: Res<GlobalState>, assets: Res
             ..Default::default()
         })
         .insert(RigidBodyPositionSync::Discrete)
-        .insert(ImmuneSystem::new(speed, health, attack))
+        .insert(immune_system)
         .insert(ScreenTag);
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn movements(
     keyboard_input: Res<Input<KeyCode>>,
     gamepads: Res<Gamepads>,
@@ -89,6 +118,9 @@ pub fn movements(
     windows: Res<Windows>,
     host_state: Res<HostState>,
     global_state: Res<GlobalState>,
+    clouds: Query<&ToxinCloud>,
+    abilities: Res<Abilities>,
+    vessels: Res<Vessels>,
 ) {
     let mut order = Vec2::ZERO;
     if keyboard_input.any_pressed([KeyCode::Right, KeyCode::D]) {
@@ -145,17 +177,30 @@ pub fn movements(
 
     let (mut rb_position, mut rb_forces, immune_system) = immune_system.single_mut();
     if order != Vec2::ZERO {
-        let position: Vec2 = rb_position.position.translation.into();
-        let distance_to_zero = (position.distance_squared(Vec2::ZERO) - 10_000.0).max(0.0);
+        let intoxication = if clouds.iter().any(|cloud| cloud.in_contact) {
+            0.5
+        } else {
+            1.0
+        };
+        let outside_vessel = vessels
+            .nearest(
+                rb_position.position.translation.into(),
+                host_state.dilatation,
+            )
+            .outside_by();
         let move_by = order.clamp_length_max(1.0)
             * immune_system.speed
-            * (1.0 - distance_to_zero / 500_000.0)
+            // never quite stuck, exits and structures can sit far from any vessel
+            * (1.0 - outside_vessel.powi(2) / (500_000.0 * arena::ARENA_SCALE.powi(2))).max(0.1)
             * (1.0 / (host_state.age / global_state.expectancy.max(50.0)).max(1.0))
+            * intoxication
+            * abilities.speed_boost()
+            * host_state.stage.speed()
             * 1000.0;
         rb_forces.force = move_by.into();
     }
     let window = windows.get_primary().unwrap();
-    let (width, height) = (window.width() * 0.985, window.height() * 0.975);
+    let (width, height) = arena::size(window);
     rb_position.position.translation.x = rb_position
         .position
         .translation
@@ -168,22 +213,49 @@ pub fn movements(
         .clamp(-height / 2.0, height / 2.0 * 0.925);
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn health(
     time: Res<Time>,
     mut immune_system: Query<(&RigidBodyPositionComponent, &mut ImmuneSystem)>,
+    fungi: Query<(&Transform, &Fungus)>,
+    cancer_cells: Query<&Transform, With<Cancer>>,
     host_state: Res<HostState>,
+    lifestyle: Res<Lifestyle>,
+    inventory: Res<Inventory>,
+    vessels: Res<Vessels>,
 ) {
     let (rb_position, mut immune_system) = immune_system.single_mut();
     let position: Vec2 = rb_position.position.translation.into();
-    let distance_to_zero =
-        position.distance_squared(Vec2::ZERO) - (host_state.dilatation / 5.0).powi(2);
-    immune_system.health -=
-        distance_to_zero.max(0.0) / 250_000.0 * time.delta_seconds() * host_state.sickness * 1.5;
-    immune_system.health = (immune_system.health
-        + (distance_to_zero.min(0.0).abs() / (host_state.dilatation / 5.0).powi(2))
-            * time.delta_seconds()
-            * host_state.regen)
-        .min(immune_system.original_health);
+    let vessel = vessels.nearest(position, host_state.dilatation);
+    immune_system.health -= vessel.outside_by().powi(2) / (250_000.0 * arena::ARENA_SCALE.powi(2))
+        * time.delta_seconds()
+        // painkillers dull the pain of leaving the vessel, not the infections behind it
+        * host_state.sickness
+        * inventory.sickness_mask()
+        * 1.5;
+    let infected = fungi.iter().any(|(transform, fungus)| {
+        transform.translation.truncate().distance_squared(position) < fungus.zone.powi(2)
+    });
+    // tumours grow their own blood supply and divert it from the vessel
+    let angiogenesis = cancer_cells
+        .iter()
+        .filter(|transform| {
+            transform.translation.truncate().distance_squared(position)
+                < ANGIOGENESIS_RADIUS.powi(2)
+        })
+        .count();
+    if infected {
+        immune_system.health -= FUNGAL_DAMAGE * time.delta_seconds();
+    } else {
+        immune_system.health = (immune_system.health
+            + (1.0 - (vessel.distance / vessel.width).powi(2)).max(0.0)
+                * time.delta_seconds()
+                * host_state.regen
+                * host_state.stage.regen()
+                * lifestyle.regen_multiplier()
+                / (1.0 + angiogenesis as f32 * 0.25))
+            .min(immune_system.original_health);
+    }
 }
 
 pub fn spawn_white_cell(
@@ -192,6 +264,7 @@ pub fn spawn_white_cell(
     time: Res<Time>,
     host: Res<HostState>,
     global: Res<GlobalState>,
+    pathogens: Query<&Pathogen>,
     assets: Res<InGameAssets>,
 ) {
     let (position, immune_system) = immune_system.single();
@@ -205,37 +278,70 @@ pub fn spawn_white_cell(
     } else {
         immune_system.attack_spawn_rate
     };
-    if rand::thread_rng().gen_bool((rate * time.delta_seconds()).clamp(0.0, 1.0) as f64) {
-        let mut velocity = RigidBodyVelocity::zero();
-        velocity.angvel = 1.0;
-
-        commands
-            .spawn_bundle(SpriteBundle {
-                transform: Transform::from_xyz(0.0, 0.0, z_layers::IMMUNE_SYSTEM),
-                texture: assets.white_cell.clone_weak(),
-                ..Default::default()
-            })
-            .insert_bundle(RigidBodyBundle {
-                position: (**position).into(),
-                damping: RigidBodyDamping {
-                    linear_damping: 15.0,
-                    angular_damping: 0.0,
-                }
-                .into(),
-                velocity: velocity.into(),
-                ..Default::default()
-            })
-            .insert_bundle(ColliderBundle {
-                collider_type: ColliderType::Sensor.into(),
-                shape: ColliderShape::ball(2.0).into(),
-                flags: ActiveEvents::INTERSECTION_EVENTS.into(),
-                ..Default::default()
-            })
-            .insert(RigidBodyPositionSync::Discrete)
-            .insert(WhiteCell {
-                spawned_at: time.seconds_since_startup() as f32,
-                strength: 50.0,
-            })
-            .insert(ScreenTag);
+    // remembered pathogens are answered faster
+    let rate = rate
+        * host
+            .memory
+            .response(pathogens.iter().map(|pathogen| pathogen.kind))
+        * host.stage.response();
+    let mut rng = rand::thread_rng();
+    if rng.gen_bool((rate * time.delta_seconds()).clamp(0.0, 1.0) as f64) {
+        let kind = *immune_system
+            .cell_types
+            .choose_weighted(&mut rng, |kind| kind.weight())
+            .unwrap();
+        spawn_white_cell_at(
+            &mut commands,
+            position.position.translation.into(),
+            kind,
+            time.seconds_since_startup() as f32,
+            immune_system.attack_damage,
+            &assets,
+        );
+    }
+}
+
+pub fn spawn_white_cell_at(
+    commands: &mut Commands,
+    position: Vec2,
+    kind: WhiteCellKind,
+    spawned_at: f32,
+    damage: f32,
+    assets: &InGameAssets,
+) -> Entity {
+    let mut velocity = RigidBodyVelocity::zero();
+    velocity.angvel = 1.0;
+
+    let white_cell = commands
+        .spawn_bundle(SpriteBundle {
+            transform: Transform::from_translation(position.extend(z_layers::IMMUNE_SYSTEM)),
+            texture: kind.texture(assets),
+            ..Default::default()
+        })
+        .insert_bundle(RigidBodyBundle {
+            position: position.into(),
+            damping: RigidBodyDamping {
+                linear_damping: 15.0,
+                angular_damping: 0.0,
+            }
+            .into(),
+            velocity: velocity.into(),
+            ..Default::default()
+        })
+        .insert_bundle(ColliderBundle {
+            collider_type: ColliderType::Sensor.into(),
+            shape: ColliderShape::ball(kind.radius()).into(),
+            flags: ActiveEvents::INTERSECTION_EVENTS.into(),
+            ..Default::default()
+        })
+        .insert(RigidBodyPositionSync::Discrete)
+        .insert(WhiteCell::new(kind, spawned_at, damage))
+        .insert(ScreenTag)
+        .id();
+    if kind == WhiteCellKind::BCell {
+        commands.entity(white_cell).insert(AntibodyProducer {
+            reload: Timer::from_seconds(1.5, true),
+        });
     }
+    white_cell
 }
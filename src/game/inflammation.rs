@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+use super::{immune_system::ImmuneSystem, white_cells::WhiteCell, HostState};
+
+pub const KILL_INFLAMMATION: f32 = 0.01;
+const WHITE_CELL_LOAD: f32 = 40.0;
+const SOOTHING: f32 = 0.04;
+const AUTOIMMUNE_DAMAGE: f32 = 0.05;
+const TISSUE_DAMAGE: f32 = 0.25;
+pub const MIN_DILATATION: f32 = 200.0;
+
+pub fn inflame(time: Res<Time>, mut host_state: ResMut<HostState>, white_cells: Query<&WhiteCell>) {
+    let load = white_cells.iter().len() as f32 / WHITE_CELL_LOAD;
+    host_state.inflammation = (host_state.inflammation
+        + (load * 0.05 * host_state.region.inflammation() - SOOTHING) * time.delta_seconds())
+    .clamp(0.0, 1.0);
+}
+
+// how red the cell is drawn, kept per cell so the colour is only written on change
+#[derive(Component)]
+pub struct Reddening(f32);
+
+pub fn overreaction(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut host_state: ResMut<HostState>,
+    mut immune_system: Query<&mut ImmuneSystem>,
+    mut white_cells: Query<(Entity, &mut Sprite, Option<&mut Reddening>), With<WhiteCell>>,
+) {
+    let overreaction = host_state.overreaction();
+    let mut immune_system = immune_system.single_mut();
+    let tint = 1.0 - overreaction * 0.7;
+    let mut attacking = 0;
+    for (entity, mut sprite, reddening) in white_cells.iter_mut() {
+        match reddening {
+            Some(mut reddening) if (reddening.0 - tint).abs() > 0.01 => {
+                reddening.0 = tint;
+                sprite.color = Color::rgb(1.0, tint, tint);
+            }
+            Some(_) => (),
+            None => {
+                commands.entity(entity).insert(Reddening(tint));
+                sprite.color = Color::rgb(1.0, tint, tint);
+            }
+        }
+        attacking += 1;
+    }
+    let attack = attacking as f32 * overreaction * time.delta_seconds();
+    // the host's own white cells turn on the immune system once inflammation runs too high
+    immune_system.health -= attack * AUTOIMMUNE_DAMAGE;
+    // and on the tissue around the blood vessel, narrowing it
+    if host_state.dilatation > MIN_DILATATION {
+        host_state.dilatation =
+            (host_state.dilatation - attack * TISSUE_DAMAGE).max(MIN_DILATATION);
+    }
+}
@@ -0,0 +1,288 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+use strum::{EnumIter, IntoEnumIterator};
+
+use crate::{
+    assets::{AudioAssets, InGameAssets},
+    progress::Progress,
+    GlobalState,
+};
+
+use super::{
+    arena,
+    director::Director,
+    immune_system::ImmuneSystem,
+    pathogens::{Pathogen, PathogenKind},
+    white_cells::WhiteCell,
+    z_layers, ScreenTag,
+};
+
+const ANTIBIOTIC_COST: f32 = 0.15;
+const ANTIBIOTIC_FRIENDLY_FIRE: f64 = 0.3;
+const PAINKILLER_DURATION: f32 = 20.0;
+const PAINKILLER_MASK: f32 = 0.3;
+const HOSPITAL_DURATION: f32 = 10.0;
+const PICKUP_RADIUS: f32 = 20.0;
+const PICKUP_INTERVAL: f32 = 45.0;
+
+#[derive(EnumIter, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Intervention {
+    AntibioticCourse,
+    Painkillers,
+    HospitalStay,
+}
+
+impl fmt::Display for Intervention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Intervention::AntibioticCourse => f.pad("Antibiotics"),
+            Intervention::Painkillers => f.pad("Painkillers"),
+            Intervention::HospitalStay => f.pad("Hospital"),
+        }
+    }
+}
+
+impl Intervention {
+    pub const fn hint(self) -> &'static str {
+        match self {
+            Intervention::AntibioticCourse => "1",
+            Intervention::Painkillers => "2",
+            Intervention::HospitalStay => "3",
+        }
+    }
+
+    const fn key(self) -> KeyCode {
+        match self {
+            Intervention::AntibioticCourse => KeyCode::Key1,
+            Intervention::Painkillers => KeyCode::Key2,
+            Intervention::HospitalStay => KeyCode::Key3,
+        }
+    }
+
+    const fn button(self) -> GamepadButtonType {
+        match self {
+            Intervention::AntibioticCourse => GamepadButtonType::LeftTrigger,
+            Intervention::Painkillers => GamepadButtonType::RightTrigger,
+            Intervention::HospitalStay => GamepadButtonType::Select,
+        }
+    }
+
+    const fn color(self) -> Color {
+        match self {
+            Intervention::AntibioticCourse => Color::rgb(1.0, 1.0, 1.0),
+            Intervention::Painkillers => Color::rgb(0.6, 0.8, 1.0),
+            Intervention::HospitalStay => Color::rgb(0.6, 1.0, 0.6),
+        }
+    }
+}
+
+pub struct Used(Intervention);
+
+pub struct Inventory {
+    stock: Vec<(Intervention, usize)>,
+    painkillers: Timer,
+    hospital: Timer,
+}
+
+impl Inventory {
+    pub fn from_progress(global_state: &GlobalState) -> Self {
+        let mut painkillers = Timer::from_seconds(PAINKILLER_DURATION, false);
+        painkillers.set_elapsed(painkillers.duration());
+        let mut hospital = Timer::from_seconds(HOSPITAL_DURATION, false);
+        hospital.set_elapsed(hospital.duration());
+        let mut inventory = Inventory {
+            stock: Intervention::iter()
+                .map(|intervention| (intervention, 0))
+                .collect(),
+            painkillers,
+            hospital,
+        };
+        if global_state.has(&Progress::Antibiotics) {
+            inventory.add(Intervention::AntibioticCourse);
+        }
+        if global_state.has(&Progress::SickDays) {
+            inventory.add(Intervention::Painkillers);
+        }
+        if global_state.has(&Progress::FreeHealthcare) {
+            inventory.add(Intervention::HospitalStay);
+        }
+        inventory
+    }
+
+    fn add(&mut self, intervention: Intervention) {
+        if let Some((_, count)) = self.stock.iter_mut().find(|(i, _)| *i == intervention) {
+            *count += 1;
+        }
+    }
+
+    pub fn slots(&self) -> impl Iterator<Item = (Intervention, usize, f32)> + '_ {
+        self.stock.iter().map(move |(intervention, count)| {
+            let active = match intervention {
+                Intervention::AntibioticCourse => 0.0,
+                Intervention::Painkillers => self.painkillers.percent_left(),
+                Intervention::HospitalStay => self.hospital.percent_left(),
+            };
+            (*intervention, *count, active)
+        })
+    }
+
+    pub fn sickness_mask(&self) -> f32 {
+        if self.painkillers.finished() {
+            1.0
+        } else {
+            PAINKILLER_MASK
+        }
+    }
+
+    pub fn hospitalised(&self) -> bool {
+        !self.hospital.finished()
+    }
+}
+
+#[derive(Component)]
+pub struct Pickup(Intervention);
+
+pub fn trigger(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    button_inputs: Res<Input<GamepadButton>>,
+    time: Res<Time>,
+    mut inventory: ResMut<Inventory>,
+    mut used: EventWriter<Used>,
+) {
+    inventory.painkillers.tick(time.delta());
+    inventory.hospital.tick(time.delta());
+    let mut activated = vec![];
+    for (intervention, count) in inventory.stock.iter_mut() {
+        let pressed = keyboard_input.just_pressed(intervention.key())
+            || gamepads.iter().any(|gamepad| {
+                button_inputs.just_pressed(GamepadButton(*gamepad, intervention.button()))
+            });
+        if pressed && *count > 0 {
+            *count -= 1;
+            activated.push(*intervention);
+        }
+    }
+    for intervention in activated {
+        match intervention {
+            Intervention::AntibioticCourse => (),
+            Intervention::Painkillers => inventory.painkillers.reset(),
+            Intervention::HospitalStay => inventory.hospital.reset(),
+        }
+        used.send(Used(intervention));
+    }
+}
+
+// antibiotics don't tell friend from foe
+pub fn antibiotics(
+    mut commands: Commands,
+    mut used: EventReader<Used>,
+    mut pathogens: Query<&mut Pathogen>,
+    mut immune_system: Query<&mut ImmuneSystem>,
+    white_cells: Query<Entity, With<WhiteCell>>,
+) {
+    for _ in used
+        .iter()
+        .filter(|Used(intervention)| *intervention == Intervention::AntibioticCourse)
+    {
+        for mut pathogen in pathogens.iter_mut() {
+            if pathogen.kind == PathogenKind::Bacteria && !pathogen.resistant {
+                let health = pathogen.health;
+                pathogen.pierce(health);
+            }
+        }
+        let mut immune_system = immune_system.single_mut();
+        immune_system.health -= immune_system.original_health * ANTIBIOTIC_COST;
+        let mut rng = rand::thread_rng();
+        for entity in white_cells.iter() {
+            if rng.gen_bool(ANTIBIOTIC_FRIENDLY_FIRE) {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+pub fn announce(
+    mut used: EventReader<Used>,
+    mut director: ResMut<Director>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+) {
+    for Used(intervention) in used.iter() {
+        director.announce(match intervention {
+            Intervention::AntibioticCourse => "Antibiotic course".to_string(),
+            Intervention::Painkillers => "Painkillers: pain eased, sickness hidden".to_string(),
+            Intervention::HospitalStay => "Hospital stay: no new infections".to_string(),
+        });
+        audio.play(
+            audio_assets.improved.clone_weak(),
+            PlaybackSettings {
+                repeat: false,
+                speed: 1.0,
+                volume: 0.2,
+            },
+        );
+    }
+}
+
+pub fn spawn_pickups(
+    mut commands: Commands,
+    time: Res<Time>,
+    windows: Res<Windows>,
+    assets: Res<InGameAssets>,
+    mut next: Local<Option<Timer>>,
+) {
+    let next = next.get_or_insert_with(|| Timer::from_seconds(PICKUP_INTERVAL, true));
+    if !next.tick(time.delta()).just_finished() {
+        return;
+    }
+    let mut rng = rand::thread_rng();
+    let (width, height) = arena::size(windows.get_primary().unwrap());
+    let position = Vec2::new(
+        rng.gen_range((-width * 0.45)..(width * 0.45)),
+        rng.gen_range((-height * 0.45)..(height * 0.4)),
+    );
+    let interventions = Intervention::iter().collect::<Vec<_>>();
+    let intervention = *interventions.choose(&mut rng).unwrap();
+    commands
+        .spawn_bundle(SpriteBundle {
+            transform: Transform::from_translation(position.extend(z_layers::STRUCTURE)),
+            sprite: Sprite {
+                color: intervention.color(),
+                custom_size: Some(Vec2::new(20.0, 12.0)),
+                ..Default::default()
+            },
+            texture: assets.capsule.clone_weak(),
+            ..Default::default()
+        })
+        .insert(Pickup(intervention))
+        .insert(ScreenTag);
+}
+
+pub fn collect(
+    mut commands: Commands,
+    mut inventory: ResMut<Inventory>,
+    immune_system: Query<&RigidBodyPositionComponent, With<ImmuneSystem>>,
+    pickups: Query<(Entity, &Transform, &Pickup)>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+) {
+    let position: Vec2 = immune_system.single().position.translation.into();
+    for (entity, transform, Pickup(intervention)) in pickups.iter() {
+        if transform.translation.truncate().distance_squared(position) < PICKUP_RADIUS.powi(2) {
+            inventory.add(*intervention);
+            commands.entity(entity).despawn_recursive();
+            audio.play(
+                audio_assets.button.clone_weak(),
+                PlaybackSettings {
+                    repeat: false,
+                    speed: 1.2,
+                    volume: 0.2,
+                },
+            );
+        }
+    }
+}
@@ -10,14 +10,7 @@ pub struct IntroPlugin;
 
 impl Plugin for IntroPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_system_set(SystemSet::on_update(GameState::Intro).with_system(maybe_skip))
-            .add_system_set(SystemSet::on_update(GameState::Intro).with_system(intro));
-    }
-}
-
-fn maybe_skip(mut state: ResMut<State<GameState>>, global_state: Res<GlobalState>) {
-    if global_state.generation > 4 {
-        let _ = state.pop();
+        app.add_system_set(SystemSet::on_update(GameState::Intro).with_system(intro));
     }
 }
 
@@ -30,7 +23,7 @@ const TEXTS: [[&str; 3]; 5] = [
     ],
     [
         "Should have mentionned...",
-        "The blood vessel is the red\nzone at the center.",
+        "Blood vessels are the red\nbranches running across the body.",
         "Oh, OK.",
     ],
     [
@@ -55,7 +48,8 @@ fn intro(
             TEXTS[global_state.generation][2],
         )
     } else {
-        ("", "", "")
+        // past the tutorial only the inherited traits are worth a look
+        ("Genome", "", "Let's go!")
     };
     egui::Window::new(RichText::new(title).color(Color32::RED))
         .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
@@ -64,7 +58,14 @@ fn intro(
         .min_width(800.0)
         .show(egui_context.ctx_mut(), |ui| {
             ui.vertical_centered(|ui| {
-                ui.label(body);
+                if !body.is_empty() {
+                    ui.label(body);
+                    ui.add_space(10.0);
+                    ui.strong("Genome");
+                }
+                for gene in global_state.genome.genes() {
+                    ui.label(format!("{}: {}", gene, gene.details()));
+                }
 
                 ui.vertical_centered_justified(|ui| {
                     ui.set_max_width(350.0);
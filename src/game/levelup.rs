@@ -135,7 +135,7 @@ impl LevelUp {
         match self {
             LevelUp::Attack => {
                 layout.append(
-                    "White cell production increase",
+                    "White cell production and damage increase, new white cell types",
                     0.0,
                     TextFormat::simple(egui::TextStyle::Small, Color32::LIGHT_GRAY),
                 );
@@ -183,6 +183,8 @@ impl LevelUp {
         match self {
             LevelUp::Attack => {
                 immune_system.attack_spawn_rate += factor;
+                immune_system.attack_damage += 20.0 * factor;
+                immune_system.unlock_cell_type();
             }
             LevelUp::Speed => {
                 immune_system.speed += 80.0 * factor;
@@ -0,0 +1,170 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+use strum::{EnumIter, IntoEnumIterator};
+
+use super::{director::Director, inflammation::MIN_DILATATION, HostState};
+
+#[derive(EnumIter, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LifestyleEvent {
+    Meal,
+    SleeplessNight,
+    Workout,
+    TripAbroad,
+}
+
+impl fmt::Display for LifestyleEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifestyleEvent::Meal => write!(f, "Meal"),
+            LifestyleEvent::SleeplessNight => write!(f, "Sleepless Night"),
+            LifestyleEvent::Workout => write!(f, "Workout"),
+            LifestyleEvent::TripAbroad => write!(f, "Trip Abroad"),
+        }
+    }
+}
+
+struct Modifier {
+    // multiplies regen, everything else is added
+    regen: f32,
+    dilatation: f32,
+    bacteria: f32,
+    virus: f32,
+    fungus: f32,
+}
+
+impl LifestyleEvent {
+    const fn duration(self) -> f32 {
+        match self {
+            LifestyleEvent::Meal => 12.0,
+            LifestyleEvent::SleeplessNight => 20.0,
+            LifestyleEvent::Workout => 15.0,
+            LifestyleEvent::TripAbroad => 30.0,
+        }
+    }
+
+    const fn description(self) -> &'static str {
+        match self {
+            LifestyleEvent::Meal => "Faster healing, but bacteria come with the food",
+            LifestyleEvent::SleeplessNight => "Slower healing and more viruses",
+            LifestyleEvent::Workout => "Wider blood vessels and faster healing",
+            LifestyleEvent::TripAbroad => "Unknown bacteria, viruses and fungi",
+        }
+    }
+
+    const fn modifier(self) -> Modifier {
+        match self {
+            LifestyleEvent::Meal => Modifier {
+                regen: 1.5,
+                dilatation: 0.0,
+                bacteria: 0.6,
+                virus: 0.0,
+                fungus: 0.0,
+            },
+            LifestyleEvent::SleeplessNight => Modifier {
+                regen: 0.5,
+                dilatation: 0.0,
+                bacteria: 0.0,
+                virus: 0.5,
+                fungus: 0.0,
+            },
+            LifestyleEvent::Workout => Modifier {
+                regen: 1.3,
+                dilatation: 300.0,
+                bacteria: 0.0,
+                virus: 0.0,
+                fungus: 0.0,
+            },
+            LifestyleEvent::TripAbroad => Modifier {
+                regen: 1.0,
+                dilatation: 0.0,
+                bacteria: 0.5,
+                virus: 0.8,
+                fungus: 0.05,
+            },
+        }
+    }
+
+    // what happens in a life depends on when it happens
+    fn weight(self, age: f32) -> f32 {
+        match (self, age as u32) {
+            (LifestyleEvent::Meal, _) => 2.0,
+            (LifestyleEvent::SleeplessNight, 0..=30) => 0.5,
+            (LifestyleEvent::SleeplessNight, 31..=150) => 2.0,
+            (LifestyleEvent::SleeplessNight, _) => 1.0,
+            (LifestyleEvent::Workout, 0..=30) => 0.5,
+            (LifestyleEvent::Workout, 31..=150) => 2.0,
+            (LifestyleEvent::Workout, _) => 0.5,
+            (LifestyleEvent::TripAbroad, 0..=30) => 0.0,
+            (LifestyleEvent::TripAbroad, 31..=200) => 1.5,
+            (LifestyleEvent::TripAbroad, _) => 0.5,
+        }
+    }
+
+    fn apply(self, host_state: &mut HostState) {
+        let modifier = self.modifier();
+        host_state.dilatation += modifier.dilatation;
+        host_state.risks.bacteria += modifier.bacteria;
+        host_state.risks.virus += modifier.virus;
+        host_state.risks.fungus += modifier.fungus;
+    }
+
+    fn revert(self, host_state: &mut HostState) {
+        let modifier = self.modifier();
+        // inflammation may have narrowed the vessels in the meantime
+        host_state.dilatation = (host_state.dilatation - modifier.dilatation).max(MIN_DILATATION);
+        host_state.risks.bacteria -= modifier.bacteria;
+        host_state.risks.virus -= modifier.virus;
+        host_state.risks.fungus -= modifier.fungus;
+    }
+}
+
+pub struct Lifestyle {
+    next: Timer,
+    current: Option<(LifestyleEvent, Timer)>,
+}
+
+impl Default for Lifestyle {
+    fn default() -> Self {
+        Lifestyle {
+            next: Timer::from_seconds(20.0, false),
+            current: None,
+        }
+    }
+}
+
+impl Lifestyle {
+    pub fn current(&self) -> Option<LifestyleEvent> {
+        self.current.as_ref().map(|(event, _)| *event)
+    }
+
+    // kept out of `HostState` so regen bonuses taken during an event survive it
+    pub fn regen_multiplier(&self) -> f32 {
+        self.current().map_or(1.0, |event| event.modifier().regen)
+    }
+}
+
+pub fn events(
+    time: Res<Time>,
+    mut lifestyle: ResMut<Lifestyle>,
+    mut host_state: ResMut<HostState>,
+    mut director: ResMut<Director>,
+) {
+    let mut rng = rand::thread_rng();
+    if let Some((event, timer)) = lifestyle.current.as_mut() {
+        if timer.tick(time.delta()).finished() {
+            event.revert(&mut host_state);
+            lifestyle.current = None;
+            lifestyle.next = Timer::from_seconds(rng.gen_range(15.0..30.0), false);
+        }
+    } else if lifestyle.next.tick(time.delta()).finished() {
+        let events = LifestyleEvent::iter().collect::<Vec<_>>();
+        let event = *events
+            .choose_weighted(&mut rng, |event| event.weight(host_state.age))
+            .unwrap();
+        event.apply(&mut host_state);
+        director.announce(format!("{}: {}", event, event.description()));
+        lifestyle.current = Some((event, Timer::from_seconds(event.duration(), false)));
+    }
+}
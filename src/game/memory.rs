@@ -0,0 +1,45 @@
+use bevy::utils::HashMap;
+
+use super::pathogens::PathogenKind;
+
+const LEARNING_RATE: f32 = 0.01;
+const INHERITANCE: f32 = 0.25;
+const MEMORY_DAMAGE: f32 = 0.5;
+const MEMORY_RESPONSE: f32 = 0.5;
+pub const VACCINE_MEMORY: f32 = 0.3;
+
+#[derive(Default, Clone, Debug)]
+pub struct ImmuneMemory(HashMap<PathogenKind, f32>);
+
+impl ImmuneMemory {
+    pub fn of(&self, kind: PathogenKind) -> f32 {
+        self.0.get(&kind).copied().unwrap_or(0.0)
+    }
+
+    pub fn learn(&mut self, kind: PathogenKind) {
+        let memory = self.0.entry(kind).or_insert(0.0);
+        *memory += (1.0 - *memory) * LEARNING_RATE;
+    }
+
+    pub fn seed(&mut self, kind: PathogenKind, memory: f32) {
+        let current = self.0.entry(kind).or_insert(0.0);
+        *current = current.max(memory);
+    }
+
+    pub fn inherited(&self) -> ImmuneMemory {
+        ImmuneMemory(
+            self.0
+                .iter()
+                .map(|(kind, memory)| (*kind, memory * INHERITANCE))
+                .collect(),
+        )
+    }
+
+    pub fn damage(&self, kind: PathogenKind, damage: f32) -> f32 {
+        damage * (1.0 + self.of(kind) * MEMORY_DAMAGE)
+    }
+
+    pub fn response(&self, kinds: impl Iterator<Item = PathogenKind>) -> f32 {
+        1.0 + kinds.map(|kind| self.of(kind)).fold(0.0, f32::max) * MEMORY_RESPONSE
+    }
+}
@@ -1,8 +1,7 @@
 use bevy::{audio::AudioSink, prelude::*};
-use strum::IntoEnumIterator;
 
 use crate::{
-    progress::{Effect, Progress},
+    progress::{Progress, Strains},
     tear_down, GameState, GlobalState, UxState,
 };
 
@@ -10,16 +9,36 @@ pub use self::host::HostState;
 use self::{
     host::{Risks, Status},
     immune_system::ImmuneSystem,
+    memory::VACCINE_MEMORY,
+    pathogens::PathogenKind,
 };
 
+mod abilities;
+mod antibodies;
+pub mod arena;
+mod director;
+mod entry_points;
+pub mod genome;
 pub mod host;
 mod immune_system;
+mod inflammation;
+mod interventions;
 mod intro;
 pub mod levelup;
+mod lifestyle;
+pub mod memory;
 mod oldest;
+mod parasites;
 mod pathogens;
+mod regions;
+mod stages;
+mod steering;
+mod structures;
 pub mod tissue;
+mod toxins;
+mod tumours;
 pub mod ui;
+mod vessel;
 mod white_cells;
 
 pub struct GamePlugin;
@@ -29,30 +48,82 @@ impl Plugin for GamePlugin {
         app.add_system_set(
             SystemSet::on_enter(GameState::Playing)
                 .with_system(setup)
-                .with_system(immune_system::setup),
+                .with_system(immune_system::setup)
+                .with_system(entry_points::setup)
+                .with_system(structures::setup)
+                .with_system(regions::setup),
         )
         .add_system_set(SystemSet::on_exit(GameState::Playing).with_system(tear_down::<ScreenTag>))
         .add_plugin(tissue::TissuePlugin)
         .add_plugin(intro::IntroPlugin)
         .add_plugin(oldest::OldestPlugin)
         .add_plugin(levelup::LevelUpPlugin)
+        .add_plugin(stages::LifeStagePlugin)
         .add_system_set(
             SystemSet::on_update(GameState::Playing)
                 .with_system(state_management)
+                .with_system(abilities::trigger)
+                .with_system(abilities::fever_pulse)
+                .with_system(abilities::fever_waves)
+                .with_system(abilities::inflammation_dash)
+                .with_system(abilities::antibody_burst)
+                .with_system(interventions::trigger)
+                .with_system(interventions::antibiotics)
+                .with_system(interventions::announce)
+                .with_system(interventions::spawn_pickups)
+                .with_system(interventions::collect)
                 .with_system(immune_system::movements)
                 .with_system(immune_system::health)
                 .with_system(immune_system::spawn_white_cell)
                 .with_system(host::aging)
+                .with_system(stages::grow_up)
                 .with_system(host::state_update)
+                .with_system(inflammation::inflame)
+                .with_system(inflammation::overreaction)
+                .with_system(director::direct)
+                .with_system(director::announcements)
+                .with_system(lifestyle::events)
                 .with_system(pathogens::spawn)
+                .with_system(entry_points::warnings)
                 .with_system(pathogens::movements)
                 .with_system(pathogens::collisions)
                 .with_system(pathogens::refresh_hit)
+                .with_system(pathogens::destroyed)
+                .with_system(pathogens::damage_state)
+                .with_system(pathogens::bacteria_fission)
                 .with_system(pathogens::cancer_replication)
+                .with_system(pathogens::fungal_growth)
+                .with_system(tumours::tumours)
+                .with_system(tumours::metastasis)
+                .with_system(parasites::spawn)
+                .with_system(parasites::movements)
+                .with_system(parasites::share_damage)
+                .with_system(parasites::defeated)
+                .with_system(toxins::release)
+                .with_system(toxins::spread)
+                .with_system(toxins::exposure)
+                .with_system(toxins::damage)
                 .with_system(white_cells::movements)
                 .with_system(white_cells::attack)
+                .with_system(structures::visit)
+                .with_system(structures::attacked)
+                .with_system(structures::lymph_nodes)
+                .with_system(structures::bone_marrow)
+                .with_system(structures::appearance)
+                .with_system(regions::travel)
+                .with_system(regions::breathing)
+                .with_system(vessel::flow)
+                .with_system(regions::labels)
+                .with_system(antibodies::produce)
+                .with_system(antibodies::aim)
+                .with_system(antibodies::expire)
+                .with_system(antibodies::hit)
+                .with_system(arena::follow)
+                .with_system(arena::minimap)
                 .with_system(ui::status),
-        );
+        )
+        .add_event::<abilities::Activated>()
+        .add_event::<interventions::Used>();
     }
 }
 
@@ -62,8 +133,13 @@ struct UiStatus;
 pub mod z_layers {
     pub const BLOODFIELD: f32 = 0.0;
     pub const TISSUE: f32 = 1.0;
+    pub const ENTRY_POINT: f32 = 1.5;
+    pub const SPAWN_WARNING: f32 = 1.6;
+    pub const STRUCTURE: f32 = 1.7;
     pub const PATHOGEN: f32 = 2.0;
+    pub const PARASITE: f32 = 2.5;
     pub const CANCER: f32 = 3.0;
+    pub const TOXIN: f32 = 3.5;
     pub const IMMUNE_SYSTEM: f32 = 4.0;
 }
 
@@ -77,6 +153,7 @@ fn setup(
     global_state: Res<GlobalState>,
     mut ux: ResMut<UxState>,
     audiosinks: Res<Assets<AudioSink>>,
+    windows: Res<Windows>,
 ) {
     audiosinks
         .get(ux.background_loop.take().unwrap())
@@ -94,18 +171,19 @@ fn setup(
     let mut regen = global_state.generation as f32 / 100.0 + global_state.progress / 5000.0;
     let mut dilatation =
         500.0 + global_state.generation as f32 * 5.0 + global_state.progress / 10.0;
-    let mut effect = Effect::default();
-    for progress in Progress::iter() {
-        if global_state.has(&progress) {
-            effect.apply(progress);
-        }
-    }
+    let effect = global_state.effect();
     bacteria += effect.bacteria;
     virus += effect.virus;
     cancer += effect.cancer;
     regen += effect.regen;
     dilatation += effect.dilatation;
 
+    let mut memory = global_state.memory.clone();
+    if global_state.has(&Progress::Vaccine) {
+        memory.seed(PathogenKind::Virus, VACCINE_MEMORY);
+    }
+
+    let seed = rand::random();
     commands.insert_resource(HostState {
         age: 0.0,
         status: Status::Healthy,
@@ -113,14 +191,33 @@ fn setup(
             bacteria,
             virus,
             cancer,
+            fungus: 0.0,
         },
         sickness: 0.0,
-        regen,
+        // penalties must not turn healing into a drain
+        regen: regen.max(0.0),
         dilatation,
         next_level_up: 25.0,
         exp: 0,
+        next_boss: 0,
+        exposure: Strains::default(),
+        inflammation: 0.0,
+        memory,
+        region: regions::Region::default(),
+        stage: stages::LifeStage::Infancy,
+        seed,
+        pending_boss_reward: false,
     });
 
+    commands.insert_resource(vessel::Vessels::generate(
+        seed,
+        arena::size(windows.get_primary().unwrap()),
+    ));
+    commands.insert_resource(director::Director::default());
+    commands.insert_resource(lifestyle::Lifestyle::default());
+    commands.insert_resource(interventions::Inventory::from_progress(&global_state));
+    commands.insert_resource(abilities::Abilities::default());
+
     let _ = state.push(GameState::Intro);
 }
 
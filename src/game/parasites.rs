@@ -0,0 +1,356 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    assets::{AudioAssets, InGameAssets},
+    GameState,
+};
+
+use super::{
+    arena,
+    host::HostState,
+    immune_system::ImmuneSystem,
+    interventions::Inventory,
+    pathogens::{spawn_bacteria, Pathogen, PathogenKind},
+    z_layers, ScreenTag,
+};
+
+const BOSSES: [(f32, Species); 3] = [
+    (120.0, Species::Worm),
+    (180.0, Species::Fluke),
+    (240.0, Species::Worm),
+];
+const HEAD_RADIUS: f32 = 16.0;
+const SEGMENT_SPACING: f32 = 20.0;
+
+#[derive(Clone, Copy)]
+pub enum Species {
+    Worm,
+    Fluke,
+}
+
+impl fmt::Display for Species {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Species::Worm => f.pad("Giant Worm"),
+            Species::Fluke => f.pad("Liver Fluke"),
+        }
+    }
+}
+
+impl Species {
+    const fn segments(self) -> usize {
+        match self {
+            Species::Worm => 7,
+            Species::Fluke => 3,
+        }
+    }
+
+    const fn health(self) -> f32 {
+        match self {
+            Species::Worm => 3000.0,
+            Species::Fluke => 1800.0,
+        }
+    }
+
+    const fn speed(self) -> f32 {
+        match self {
+            Species::Worm => 30.0,
+            Species::Fluke => 55.0,
+        }
+    }
+
+    const fn patterns(self) -> &'static [Pattern] {
+        match self {
+            Species::Worm => &[Pattern::Stalk, Pattern::Charge, Pattern::Brood],
+            Species::Fluke => &[Pattern::Stalk, Pattern::Coil, Pattern::Charge],
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Pattern {
+    Stalk,
+    Charge,
+    Coil,
+    Brood,
+}
+
+impl Pattern {
+    const fn duration(self) -> f32 {
+        match self {
+            Pattern::Stalk => 4.0,
+            Pattern::Charge => 1.5,
+            Pattern::Coil => 5.0,
+            Pattern::Brood => 1.0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Parasite {
+    pub species: Species,
+    step: usize,
+    pattern_timer: Timer,
+}
+
+impl Parasite {
+    fn pattern(&self) -> Pattern {
+        let patterns = self.species.patterns();
+        patterns[self.step % patterns.len()]
+    }
+}
+
+#[derive(Component)]
+pub struct ParasiteSegment {
+    head: Entity,
+    leader: Entity,
+    synced_health: f32,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn spawn(
+    mut commands: Commands,
+    mut host_state: ResMut<HostState>,
+    inventory: Res<Inventory>,
+    immune_system: Query<&Transform, With<ImmuneSystem>>,
+    windows: Res<Windows>,
+    assets: Res<InGameAssets>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+) {
+    if inventory.hospitalised() {
+        return;
+    }
+    if let Some((age, species)) = BOSSES.get(host_state.next_boss) {
+        if host_state.age > *age {
+            host_state.next_boss += 1;
+            let (width, height) = arena::size(windows.get_primary().unwrap());
+            let player = immune_system.single().translation.truncate();
+            let position = Vec2::new(-player.x.signum() * width * 0.35, height * 0.3);
+            spawn_parasite(
+                &mut commands,
+                *species,
+                position,
+                assets.parasite.clone_weak(),
+            );
+            audio.play(
+                audio_assets.pathogen_spawn.clone_weak(),
+                PlaybackSettings {
+                    repeat: false,
+                    volume: 0.3,
+                    speed: 0.4,
+                },
+            );
+        }
+    }
+}
+
+fn spawn_parasite(
+    commands: &mut Commands,
+    species: Species,
+    position: Vec2,
+    texture: Handle<Image>,
+) {
+    let mut pathogen = Pathogen::new(PathogenKind::Parasite, species.speed(), 15.0);
+    pathogen.health = species.health();
+    pathogen.original_health = species.health();
+    let head = commands
+        .spawn_bundle(SpriteBundle {
+            transform: Transform::from_translation(position.extend(z_layers::PARASITE)),
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(HEAD_RADIUS * 2.5)),
+                ..Default::default()
+            },
+            texture: texture.clone(),
+            ..Default::default()
+        })
+        .insert_bundle(RigidBodyBundle {
+            position: position.into(),
+            mass_properties: RigidBodyMassPropsFlags::ROTATION_LOCKED.into(),
+            damping: RigidBodyDamping {
+                linear_damping: 10.0,
+                angular_damping: 10.0,
+            }
+            .into(),
+            ..Default::default()
+        })
+        .insert_bundle(ColliderBundle {
+            shape: ColliderShape::ball(HEAD_RADIUS).into(),
+            flags: ColliderFlags {
+                solver_groups: InteractionGroups::new(4, u32::MAX ^ 4),
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        })
+        .insert(RigidBodyPositionSync::Discrete)
+        .insert(pathogen)
+        .insert(Parasite {
+            species,
+            step: 0,
+            pattern_timer: Timer::from_seconds(Pattern::Stalk.duration(), false),
+        })
+        .insert(ScreenTag)
+        .id();
+
+    let mut leader = head;
+    for i in 0..species.segments() {
+        let radius = HEAD_RADIUS * (1.0 - (i + 1) as f32 / (species.segments() as f32 + 2.0));
+        let position = position + Vec2::new(0.0, (i + 1) as f32 * SEGMENT_SPACING);
+        let mut pathogen = Pathogen::new(PathogenKind::Parasite, species.speed(), 10.0);
+        pathogen.health = species.health();
+        pathogen.original_health = species.health();
+        leader = commands
+            .spawn_bundle(SpriteBundle {
+                transform: Transform::from_translation(position.extend(z_layers::PARASITE)),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(radius * 2.5)),
+                    ..Default::default()
+                },
+                texture: texture.clone(),
+                ..Default::default()
+            })
+            .insert_bundle(RigidBodyBundle {
+                position: position.into(),
+                damping: RigidBodyDamping {
+                    linear_damping: 10.0,
+                    angular_damping: 10.0,
+                }
+                .into(),
+                ..Default::default()
+            })
+            .insert_bundle(ColliderBundle {
+                shape: ColliderShape::ball(radius).into(),
+                flags: ColliderFlags {
+                    solver_groups: InteractionGroups::new(4, u32::MAX ^ 4),
+                    ..Default::default()
+                }
+                .into(),
+                ..Default::default()
+            })
+            .insert(RigidBodyPositionSync::Discrete)
+            .insert(pathogen)
+            .insert(ParasiteSegment {
+                head,
+                leader,
+                synced_health: species.health(),
+            })
+            .insert(ScreenTag)
+            .id();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn movements(
+    mut commands: Commands,
+    time: Res<Time>,
+    immune_system: Query<&Transform, With<ImmuneSystem>>,
+    mut heads: Query<(
+        &RigidBodyPositionComponent,
+        &mut RigidBodyForcesComponent,
+        &Pathogen,
+        &mut Parasite,
+    )>,
+    mut segments: Query<
+        (
+            &RigidBodyPositionComponent,
+            &mut RigidBodyForcesComponent,
+            &ParasiteSegment,
+        ),
+        Without<Parasite>,
+    >,
+    leaders: Query<&RigidBodyPositionComponent, Or<(With<Parasite>, With<ParasiteSegment>)>>,
+    assets: Res<InGameAssets>,
+) {
+    let target = immune_system.single().translation.truncate();
+    for (rb_position, mut rb_forces, pathogen, mut parasite) in heads.iter_mut() {
+        let position: Vec2 = rb_position.position.translation.into();
+        if parasite.pattern_timer.tick(time.delta()).just_finished() {
+            parasite.step += 1;
+            parasite.pattern_timer = Timer::from_seconds(parasite.pattern().duration(), false);
+            if parasite.pattern() == Pattern::Brood {
+                for i in 0..3 {
+                    let angle = i as f32 * std::f32::consts::TAU / 3.0;
+                    spawn_bacteria(
+                        &mut commands,
+                        position + Vec2::new(angle.cos(), angle.sin()) * HEAD_RADIUS * 2.0,
+                        std::time::Duration::from_millis(1000),
+                        assets.bacteria.clone_weak(),
+                        false,
+                    );
+                }
+            }
+        }
+        let order = (target - position).normalize_or_zero();
+        let move_by = match parasite.pattern() {
+            Pattern::Stalk => order * pathogen.speed,
+            Pattern::Charge => order * pathogen.speed * 5.0,
+            Pattern::Coil => order.perp() * pathogen.speed * 1.5 + order * pathogen.speed * 0.3,
+            Pattern::Brood => Vec2::ZERO,
+        };
+        rb_forces.force = (move_by * 4000.0).into();
+    }
+
+    for (rb_position, mut rb_forces, segment) in segments.iter_mut() {
+        if let Ok(leader) = leaders.get(segment.leader) {
+            let position: Vec2 = rb_position.position.translation.into();
+            let leader: Vec2 = leader.position.translation.into();
+            let order = leader - position;
+            let stretch = order.length() - SEGMENT_SPACING;
+            rb_forces.force = (order.normalize_or_zero() * stretch * 3000.0).into();
+        }
+    }
+}
+
+// hits on the body only deal a quarter of their damage to the head
+pub fn share_damage(
+    mut heads: Query<&mut Pathogen, With<Parasite>>,
+    mut segments: Query<(&mut Pathogen, &mut ParasiteSegment), Without<Parasite>>,
+) {
+    for (mut pathogen, mut segment) in segments.iter_mut() {
+        if let Ok(mut head) = heads.get_mut(segment.head) {
+            head.health -= (segment.synced_health - pathogen.health) * 0.25;
+            // the body stays alive until the head is cleaned up
+            pathogen.health = head.health.max(1.0);
+            segment.synced_health = pathogen.health;
+        }
+    }
+}
+
+pub fn defeated(
+    mut commands: Commands,
+    heads: Query<&Parasite>,
+    segments: Query<(Entity, &ParasiteSegment)>,
+    mut host_state: ResMut<HostState>,
+    mut state: ResMut<State<GameState>>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+) {
+    let mut defeated = vec![];
+    for (entity, segment) in segments.iter() {
+        if heads.get(segment.head).is_err() {
+            commands.entity(entity).despawn_recursive();
+            if !defeated.contains(&segment.head) {
+                defeated.push(segment.head);
+            }
+        }
+    }
+    if !defeated.is_empty() {
+        audio.play(
+            audio_assets.won.clone_weak(),
+            PlaybackSettings {
+                repeat: false,
+                speed: 1.0,
+                volume: 0.3,
+            },
+        );
+        host_state.pending_boss_reward = true;
+    }
+    // another state change may already be queued this frame, try again until it goes through
+    if host_state.pending_boss_reward && state.push(GameState::LevelUp).is_ok() {
+        host_state.pending_boss_reward = false;
+    }
+}
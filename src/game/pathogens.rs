@@ -3,250 +3,428 @@ use std::time::Duration;
 use bevy::prelude::*;
 use bevy_easings::{Ease, EaseFunction, EasingComponent, EasingType};
 use bevy_rapier2d::prelude::*;
-use rand::Rng;
+use rand::{seq::SliceRandom, Rng};
 
-use crate::assets::{AudioAssets, InGameAssets};
+use crate::{
+    assets::{AudioAssets, InGameAssets},
+    GlobalState,
+};
 
-use super::{host::HostState, immune_system::ImmuneSystem, z_layers, ScreenTag};
+use super::{
+    arena,
+    director::Director,
+    entry_points::{self, EntryPoint, SpawnWarning},
+    host::HostState,
+    immune_system::ImmuneSystem,
+    inflammation::KILL_INFLAMMATION,
+    interventions::Inventory,
+    parasites::ParasiteSegment,
+    steering::{Neighbour, Steering},
+    structures::Structure,
+    toxins::Toxic,
+    tumours::Metastasis,
+    vessel::{Vessels, VESSEL_CLEARANCE},
+    white_cells::WhiteCell,
+    z_layers, ScreenTag,
+};
+
+const MAX_BACTERIA: usize = 150;
+pub const MAX_FUNGAL_ZONES: usize = 8;
+const MAX_FUNGAL_ZONE_RADIUS: f32 = 140.0;
+// how close a pathogen must be to a standing structure to go after it instead of the immune system
+const STRUCTURE_AGGRO: f32 = 200.0;
+// seconds a bacterium holds off dividing after taking damage
+const WOUND_RECOVERY: f32 = 3.0;
 
 #[derive(Component)]
-pub struct Bacteria;
+pub struct Bacteria {
+    division: Timer,
+}
 
 #[derive(Component)]
 pub struct Virus;
 
 #[derive(Component)]
 pub struct Cancer {
-    replication: f32,
+    pub replication: f32,
+    // the first cell of the tumour, `None` for that first cell
+    pub tumour: Option<Entity>,
+}
+
+#[derive(Component)]
+pub struct Fungus {
+    pub zone: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum PathogenKind {
+    Bacteria,
+    Virus,
+    Cancer,
+    Fungus,
+    Parasite,
+}
+
+impl PathogenKind {
+    pub const fn health(self) -> f32 {
+        match self {
+            PathogenKind::Bacteria => 60.0,
+            PathogenKind::Virus => 15.0,
+            PathogenKind::Cancer => 600.0,
+            PathogenKind::Fungus => 120.0,
+            PathogenKind::Parasite => 3000.0,
+        }
+    }
+
+    pub const fn armor(self) -> f32 {
+        match self {
+            PathogenKind::Bacteria => 5.0,
+            PathogenKind::Virus => 0.0,
+            PathogenKind::Cancer => 20.0,
+            PathogenKind::Fungus => 10.0,
+            PathogenKind::Parasite => 15.0,
+        }
+    }
+
+    pub const fn exp(self) -> usize {
+        match self {
+            PathogenKind::Bacteria => 1,
+            PathogenKind::Virus => 1,
+            PathogenKind::Cancer => 10,
+            PathogenKind::Fungus => 3,
+            PathogenKind::Parasite => 50,
+        }
+    }
 }
 
 #[derive(Component)]
 pub struct Pathogen {
+    pub kind: PathogenKind,
     pub strength: f32,
+    pub health: f32,
+    pub original_health: f32,
+    pub armor: f32,
+    pub resistant: bool,
     speed: f32,
     last_hit: Timer,
     in_contact: bool,
+    // time left before recovering from the last damage taken
+    wounded: f32,
+}
+
+impl Pathogen {
+    pub fn new(kind: PathogenKind, speed: f32, strength: f32) -> Pathogen {
+        Pathogen {
+            kind,
+            strength,
+            health: kind.health(),
+            original_health: kind.health(),
+            armor: kind.armor(),
+            resistant: false,
+            speed,
+            last_hit: Timer::from_seconds(1.0, true),
+            in_contact: false,
+            wounded: 0.0,
+        }
+    }
+
+    fn with_resistance(mut self, resistant: bool) -> Pathogen {
+        if resistant {
+            self.resistant = true;
+            self.armor += 5.0;
+        }
+        self
+    }
+
+    pub fn hit(&mut self, damage: f32) {
+        self.health -= (damage - self.armor).max(1.0);
+        self.wounded = WOUND_RECOVERY;
+    }
+
+    pub fn pierce(&mut self, damage: f32) {
+        self.health -= damage;
+        self.wounded = WOUND_RECOVERY;
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0.0
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Incoming {
+    pub kind: PathogenKind,
+    pub toxic: bool,
+    pub resistant: bool,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn spawn(
     mut commands: Commands,
     state: Res<HostState>,
+    global_state: Res<GlobalState>,
     time: Res<Time>,
     windows: Res<Windows>,
+    director: Res<Director>,
+    inventory: Res<Inventory>,
+    vessels: Res<Vessels>,
+    entry_points: Query<(&Transform, &EntryPoint)>,
+    fungi: Query<&Transform, With<Fungus>>,
+    warnings: Query<(&Transform, &SpawnWarning)>,
     assets: Res<InGameAssets>,
     audio_assets: Res<AudioAssets>,
     audio: Res<Audio>,
 ) {
+    if inventory.hospitalised() {
+        return;
+    }
     let mut rng = rand::thread_rng();
+    let mut incoming = vec![];
     if rng.gen_bool(
-        ((state.risks.bacteria + state.age / 400.0) * time.delta_seconds()).clamp(0.0, 1.0) as f64,
+        ((state.risks.bacteria + state.age / 400.0)
+            * director.multiplier(PathogenKind::Bacteria)
+            * state.region.mix(PathogenKind::Bacteria)
+            * time.delta_seconds())
+        .clamp(0.0, 1.0) as f64,
     ) {
-        let window = windows.get_primary().unwrap();
-        let (width, height) = (window.width() * 0.985, window.height() * 0.975);
-        let position = std::iter::repeat_with(|| {
-            Vec2::new(
-                rng.gen_range((-width / 2.0)..(width / 2.0)),
-                rng.gen_range((-height / 2.0)..(height / 2.0 * 0.9)),
-            )
-        })
-        .find(|pos| pos.distance_squared(Vec2::ZERO) > 50_000.0)
-        .unwrap();
-        let mut velocity = RigidBodyVelocity::zero();
-        velocity.angvel = rng.gen_range(-0.5..0.5);
-        commands.spawn_bundle(PathogenBundle {
-            sprite: SpriteBundle {
-                transform: Transform {
-                    translation: position.extend(z_layers::PATHOGEN),
-                    scale: Vec2::ZERO.extend(1.0),
-                    rotation: Quat::IDENTITY,
-                },
-                sprite: Sprite {
-                    color: Color::WHITE,
-                    flip_x: rng.gen_bool(0.5),
-                    flip_y: rng.gen_bool(0.5),
-                    custom_size: None,
-                },
-                texture: assets.bacteria.clone_weak(),
-                ..Default::default()
-            },
-            rigid_body: RigidBodyBundle {
-                position: position.into(),
-                damping: RigidBodyDamping {
-                    linear_damping: 15.0,
-                    angular_damping: 0.0,
-                }
-                .into(),
-                velocity: velocity.into(),
-                ..Default::default()
-            },
-            collider: ColliderBundle {
-                shape: ColliderShape::ball(8.0).into(),
-                flags: ColliderFlags {
-                    solver_groups: InteractionGroups::new(1, 1),
-                    ..Default::default()
-                }
-                .into(),
-                ..Default::default()
-            },
-            position_sync: RigidBodyPositionSync::Discrete,
-            pathogen_spec: Bacteria,
-            pathogen: Pathogen {
-                speed: 50.0,
-                strength: 10.0,
-                last_hit: Timer::from_seconds(1.0, true),
-                in_contact: false,
-            },
-            tag: ScreenTag,
-            easing: Transform {
-                translation: position.extend(z_layers::PATHOGEN),
-                scale: Vec2::ZERO.extend(1.0),
-                rotation: Quat::IDENTITY,
-            }
-            .ease_to(
-                Transform {
-                    translation: position.extend(z_layers::PATHOGEN),
-                    scale: Vec3::ONE,
-                    rotation: Quat::IDENTITY,
-                },
-                EaseFunction::CubicOut,
-                EasingType::Once {
-                    duration: Duration::from_millis(4000),
-                },
-            ),
+        incoming.push(Incoming {
+            kind: PathogenKind::Bacteria,
+            toxic: state.age > 40.0 && rng.gen_bool(state.region.toxic_chance()),
+            resistant: rng.gen_bool(global_state.bacteria_resistance() as f64),
         });
-        audio.play(
-            audio_assets.pathogen_spawn.clone_weak(),
-            PlaybackSettings {
-                repeat: false,
-                volume: 0.15,
-                speed: 1.75,
-            },
-        );
     }
     if rng.gen_bool(
-        ((state.risks.virus + state.age / 400.0) * time.delta_seconds()).clamp(0.0, 1.0) as f64,
+        ((state.risks.virus + state.age / 400.0)
+            * director.multiplier(PathogenKind::Virus)
+            * state.region.mix(PathogenKind::Virus)
+            * time.delta_seconds())
+        .clamp(0.0, 1.0) as f64,
     ) {
-        let window = windows.get_primary().unwrap();
-        let (width, height) = (window.width() * 0.985, window.height() * 0.975);
-        let position = std::iter::repeat_with(|| {
-            Vec2::new(
-                rng.gen_range((-width / 2.0)..(width / 2.0)),
-                rng.gen_range((-height / 2.0)..(height / 2.0 * 0.9)),
-            )
-        })
-        .find(|pos| pos.distance_squared(Vec2::ZERO) > 50_000.0)
-        .unwrap();
-        let mut velocity = RigidBodyVelocity::zero();
-        velocity.angvel = rng.gen_range(-1.5..1.5);
-        commands.spawn_bundle(PathogenBundle {
-            sprite: SpriteBundle {
-                transform: Transform {
-                    translation: position.extend(z_layers::PATHOGEN),
-                    scale: Vec2::ZERO.extend(1.0),
-                    rotation: Quat::IDENTITY,
-                },
-                sprite: Sprite {
-                    color: Color::WHITE,
-                    flip_x: rng.gen_bool(0.5),
-                    flip_y: rng.gen_bool(0.5),
-                    custom_size: None,
-                },
-                texture: assets.virus.clone_weak(),
-                ..Default::default()
-            },
-            rigid_body: RigidBodyBundle {
-                position: position.into(),
-                damping: RigidBodyDamping {
-                    linear_damping: 15.0,
-                    angular_damping: 0.0,
-                }
-                .into(),
-                velocity: velocity.into(),
-                ..Default::default()
-            },
-            collider: ColliderBundle {
-                shape: ColliderShape::ball(5.0).into(),
-                flags: ColliderFlags {
-                    solver_groups: InteractionGroups::new(1, 1),
-                    ..Default::default()
-                }
-                .into(),
-                ..Default::default()
-            },
-            position_sync: RigidBodyPositionSync::Discrete,
-            pathogen_spec: Bacteria,
-            pathogen: Pathogen {
-                speed: 75.0,
-                strength: 2.0,
-                last_hit: Timer::from_seconds(1.0, true),
-                in_contact: false,
-            },
-            tag: ScreenTag,
-            easing: Transform {
-                translation: position.extend(z_layers::PATHOGEN),
-                scale: Vec2::ZERO.extend(1.0),
-                rotation: Quat::IDENTITY,
-            }
-            .ease_to(
-                Transform {
-                    translation: position.extend(z_layers::PATHOGEN),
-                    scale: Vec3::ONE,
-                    rotation: Quat::IDENTITY,
-                },
-                EaseFunction::CubicOut,
-                EasingType::Once {
-                    duration: Duration::from_millis(4000),
-                },
-            ),
+        incoming.push(Incoming {
+            kind: PathogenKind::Virus,
+            toxic: false,
+            resistant: rng.gen_bool(global_state.virus_resistance() as f64),
         });
-        audio.play(
-            audio_assets.pathogen_spawn.clone_weak(),
-            PlaybackSettings {
-                repeat: false,
-                volume: 0.15,
-                speed: 1.4,
-            },
-        );
     }
     if rng.gen_bool(
-        ((state.risks.cancer + state.age.min(100.0) / 1000.0) * time.delta_seconds())
-            .clamp(0.0, 1.0) as f64,
+        ((state.risks.cancer + state.age.min(100.0) / 1000.0)
+            * director.multiplier(PathogenKind::Cancer)
+            * state.region.mix(PathogenKind::Cancer)
+            * time.delta_seconds())
+        .clamp(0.0, 1.0) as f64,
     ) {
-        let window = windows.get_primary().unwrap();
-        let (width, height) = (window.width() * 0.985, window.height() * 0.975);
-        let position = std::iter::repeat_with(|| {
-            Vec2::new(
-                rng.gen_range((-width / 2.0)..(width / 2.0)),
-                rng.gen_range((-height / 2.0)..(height / 2.0 * 0.9)),
-            )
-        })
-        .find(|pos| pos.distance_squared(Vec2::ZERO) > 80_000.0)
-        .unwrap();
-        spawn_cancer_cell(&mut commands, position, 0.12, assets.cancer.clone_weak());
+        incoming.push(Incoming {
+            kind: PathogenKind::Cancer,
+            toxic: false,
+            resistant: false,
+        });
+    }
+    // queued spores count too, the tissue shader only has room for so many zones
+    let fungal_zones = fungi
+        .iter()
+        .chain(
+            warnings
+                .iter()
+                .filter(|(_, warning)| warning.kind() == PathogenKind::Fungus)
+                .map(|(transform, _)| transform),
+        )
+        .map(|transform| transform.translation.truncate())
+        .collect::<Vec<_>>();
+    if fungal_zones.len() < MAX_FUNGAL_ZONES
+        && rng.gen_bool(
+            ((state.risks.fungus + state.age.min(150.0) / 3000.0)
+                * director.multiplier(PathogenKind::Fungus)
+                * state.region.mix(PathogenKind::Fungus)
+                * time.delta_seconds())
+            .clamp(0.0, 1.0) as f64,
+        )
+    {
+        incoming.push(Incoming {
+            kind: PathogenKind::Fungus,
+            toxic: false,
+            resistant: false,
+        });
+    }
+
+    let entry_points = entry_points.iter().collect::<Vec<_>>();
+    let (width, height) = arena::size(windows.get_primary().unwrap());
+    let on_tissue = |rng: &mut rand::rngs::ThreadRng| {
+        Vec2::new(
+            rng.gen_range((-width / 2.0)..(width / 2.0)),
+            rng.gen_range((-height / 2.0)..(height / 2.0 * 0.9)),
+        )
+    };
+    let away_from_vessels =
+        |pos: &Vec2| vessels.nearest(*pos, state.dilatation).distance > VESSEL_CLEARANCE;
+    for incoming in incoming {
+        // cancer and fungus grow on the host's tissue, everything else comes in from outside
+        let position = if incoming.kind == PathogenKind::Cancer {
+            std::iter::repeat_with(|| on_tissue(&mut rng))
+                .find(away_from_vessels)
+                .unwrap()
+        } else if incoming.kind == PathogenKind::Fungus {
+            // zones shouldn't overlap, give up on a crowded host
+            match std::iter::repeat_with(|| on_tissue(&mut rng))
+                .take(50)
+                .filter(away_from_vessels)
+                .find(|pos| {
+                    fungal_zones
+                        .iter()
+                        .all(|zone| zone.distance(*pos) > MAX_FUNGAL_ZONE_RADIUS)
+                }) {
+                Some(position) => position,
+                None => continue,
+            }
+        } else if let Ok((transform, _)) =
+            entry_points.choose_weighted(&mut rng, |(_, entry_point)| {
+                entry_point.kind.weight(incoming.kind) * state.region.entry_weight(entry_point.kind)
+            })
+        {
+            transform.translation.truncate()
+                + Vec2::new(rng.gen_range(-12.0..12.0), rng.gen_range(-12.0..12.0))
+        } else {
+            continue;
+        };
+        entry_points::telegraph(
+            &mut commands,
+            incoming,
+            position,
+            assets.entry_point.clone_weak(),
+        );
+        let (volume, speed) = match incoming.kind {
+            PathogenKind::Bacteria => (0.15, 1.75),
+            PathogenKind::Virus => (0.15, 1.4),
+            PathogenKind::Cancer => (0.1, 0.75),
+            _ => (0.1, 0.5),
+        };
         audio.play(
             audio_assets.pathogen_spawn.clone_weak(),
             PlaybackSettings {
                 repeat: false,
-                volume: 0.1,
-                speed: 0.75,
+                volume,
+                speed,
             },
         );
     }
 }
 
+pub fn spawn_incoming(
+    commands: &mut Commands,
+    incoming: Incoming,
+    position: Vec2,
+    assets: &InGameAssets,
+) {
+    match incoming.kind {
+        PathogenKind::Bacteria if incoming.toxic => {
+            let bacteria = spawn_bacteria(
+                commands,
+                position,
+                Duration::from_millis(4000),
+                assets.toxic_bacteria.clone_weak(),
+                incoming.resistant,
+            );
+            commands
+                .entity(bacteria)
+                .insert(Toxic {
+                    release: Timer::from_seconds(6.0, true),
+                })
+                .insert(Steering::FLANK);
+        }
+        PathogenKind::Bacteria => {
+            spawn_bacteria(
+                commands,
+                position,
+                Duration::from_millis(4000),
+                assets.bacteria.clone_weak(),
+                incoming.resistant,
+            );
+        }
+        PathogenKind::Virus => {
+            spawn_virus(
+                commands,
+                position,
+                assets.virus.clone_weak(),
+                incoming.resistant,
+            );
+        }
+        PathogenKind::Cancer => {
+            spawn_cancer_cell(commands, position, 0.12, None, assets.cancer.clone_weak());
+        }
+        PathogenKind::Fungus => {
+            spawn_fungal_spore(commands, position, assets.fungus.clone_weak());
+        }
+        // bosses arrive on their own schedule
+        PathogenKind::Parasite => (),
+    }
+}
+
+#[allow(clippy::type_complexity)]
 pub fn movements(
     immune_system: Query<&Transform, With<ImmuneSystem>>,
-    mut pathogens: Query<(
+    host_state: Res<HostState>,
+    vessels: Res<Vessels>,
+    mut pathogens: Query<
+        (
+            Entity,
+            &RigidBodyPositionComponent,
+            &mut RigidBodyForcesComponent,
+            &Pathogen,
+            &Steering,
+        ),
+        Without<Metastasis>,
+    >,
+    neighbours: Query<(
+        Entity,
         &RigidBodyPositionComponent,
-        &mut RigidBodyForcesComponent,
+        &RigidBodyVelocityComponent,
         &Pathogen,
     )>,
+    white_cells: Query<&RigidBodyPositionComponent, With<WhiteCell>>,
+    structures: Query<(&Transform, &Structure)>,
 ) {
-    let target = immune_system.single().translation.truncate();
-    for (rb_position, mut rb_forces, pathogen) in pathogens.iter_mut() {
-        let position: Vec2 = rb_position.position.translation.into();
-        let order = target - position;
-        let move_by = order.normalize() * pathogen.speed * 1000.0;
+    let immune_system = immune_system.single().translation.truncate();
+    let vessel = vessels.nearest(immune_system, host_state.dilatation).point;
+    let structures = structures
+        .iter()
+        .filter(|(_, structure)| !structure.destroyed)
+        .map(|(transform, _)| transform.translation.truncate())
+        .collect::<Vec<_>>();
+    let neighbours = neighbours
+        .iter()
+        .map(|(entity, rb_position, rb_velocity, pathogen)| Neighbour {
+            entity,
+            kind: pathogen.kind,
+            position: rb_position.position.translation.into(),
+            velocity: rb_velocity.linvel.into(),
+        })
+        .collect::<Vec<_>>();
+    let threats = white_cells
+        .iter()
+        .map(|rb_position| Vec2::from(rb_position.position.translation))
+        .collect::<Vec<_>>();
+    for (entity, rb_position, mut rb_forces, pathogen, steering) in pathogens.iter_mut() {
+        let me = Neighbour {
+            entity,
+            kind: pathogen.kind,
+            position: rb_position.position.translation.into(),
+            velocity: Vec2::ZERO,
+        };
+        let target = structures
+            .iter()
+            .copied()
+            .filter(|structure| {
+                let distance = structure.distance_squared(me.position);
+                distance < STRUCTURE_AGGRO.powi(2)
+                    && distance < immune_system.distance_squared(me.position)
+            })
+            .min_by(|a, b| {
+                a.distance_squared(me.position)
+                    .partial_cmp(&b.distance_squared(me.position))
+                    .unwrap()
+            })
+            .unwrap_or(immune_system);
+        let order = steering.direction(&me, target, vessel, &neighbours, &threats);
+        let move_by = order * pathogen.speed * 1000.0;
         rb_forces.force = move_by.into();
     }
 }
@@ -307,16 +485,127 @@ pub fn refresh_hit(
     }
 }
 
+pub fn destroyed(
+    mut commands: Commands,
+    // segments live as long as their head, `parasites::defeated` cleans them up
+    pathogens: Query<(Entity, &Pathogen), Without<ParasiteSegment>>,
+    mut host_state: ResMut<HostState>,
+    global_state: Res<GlobalState>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+) {
+    let counter_measures = global_state.counter_measures();
+    for (entity, pathogen) in pathogens.iter() {
+        if pathogen.is_dead() {
+            commands.entity(entity).despawn_recursive();
+            host_state.exp +=
+                (pathogen.kind.exp() as f32 * host_state.region.exp_multiplier()).round() as usize;
+            host_state.memory.learn(pathogen.kind);
+            host_state.inflammation = (host_state.inflammation + KILL_INFLAMMATION).min(1.0);
+            match pathogen.kind {
+                PathogenKind::Bacteria => {
+                    host_state.exposure.bacteria += counter_measures.bacteria;
+                }
+                PathogenKind::Virus => {
+                    host_state.exposure.virus += counter_measures.virus;
+                }
+                _ => (),
+            }
+            audio.play(
+                audio_assets.pathogen_destroyed.clone_weak(),
+                PlaybackSettings {
+                    repeat: false,
+                    speed: 1.0,
+                    volume: 0.2,
+                },
+            );
+        }
+    }
+}
+
+pub fn damage_state(mut pathogens: Query<(&Pathogen, &mut Sprite)>) {
+    for (pathogen, mut sprite) in pathogens.iter_mut() {
+        let health = (pathogen.health / pathogen.original_health).clamp(0.0, 1.0);
+        if pathogen.resistant {
+            sprite.color = Color::rgb(0.75, 0.2 + 0.4 * health, 0.5 + 0.5 * health);
+        } else {
+            sprite.color = Color::rgb(1.0, 0.3 + 0.7 * health, 0.3 + 0.7 * health);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn bacteria_fission(
+    mut commands: Commands,
+    time: Res<Time>,
+    state: Res<HostState>,
+    inventory: Res<Inventory>,
+    mut bacteria: Query<(&Transform, &mut Pathogen, &mut Bacteria)>,
+    assets: Res<InGameAssets>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+) {
+    if inventory.hospitalised() {
+        return;
+    }
+    let mut population = bacteria.iter().len();
+    let growth = state.risks.bacteria.max(0.1) * (1.0 + state.sickness * 2.0);
+    let mut rng = rand::thread_rng();
+    for (transform, mut pathogen, mut bacteria) in bacteria.iter_mut() {
+        pathogen.wounded = (pathogen.wounded - time.delta_seconds()).max(0.0);
+        // a colony only snowballs while it's left alone
+        if pathogen.in_contact || pathogen.wounded > 0.0 {
+            continue;
+        }
+        if bacteria
+            .division
+            .tick(time.delta().mul_f32(growth))
+            .just_finished()
+            && population < MAX_BACTERIA
+        {
+            population += 1;
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let position =
+                transform.translation.truncate() + Vec2::new(angle.cos(), angle.sin()) * 12.0;
+            spawn_bacteria(
+                &mut commands,
+                position,
+                Duration::from_millis(1500),
+                assets.bacteria.clone_weak(),
+                pathogen.resistant,
+            );
+            audio.play(
+                audio_assets.pathogen_spawn.clone_weak(),
+                PlaybackSettings {
+                    repeat: false,
+                    volume: 0.05,
+                    speed: 2.0,
+                },
+            );
+        }
+    }
+}
+
+pub fn fungal_growth(time: Res<Time>, mut fungi: Query<&mut Fungus>) {
+    for mut fungus in fungi.iter_mut() {
+        fungus.zone = (fungus.zone + time.delta_seconds() * 4.0).min(MAX_FUNGAL_ZONE_RADIUS);
+    }
+}
+
 pub fn cancer_replication(
     mut commands: Commands,
     time: Res<Time>,
-    mut cancer_cells: Query<(&Transform, &mut Cancer)>,
+    inventory: Res<Inventory>,
+    mut cancer_cells: Query<(Entity, &Transform, &mut Cancer)>,
     assets: Res<InGameAssets>,
     audio_assets: Res<AudioAssets>,
     audio: Res<Audio>,
 ) {
+    if inventory.hospitalised() {
+        return;
+    }
     let mut rng = rand::thread_rng();
-    for (transform, mut cancer) in cancer_cells.iter_mut() {
+    for (entity, transform, mut cancer) in cancer_cells.iter_mut() {
         if rng.gen_bool((cancer.replication * time.delta_seconds()).clamp(0.0, 1.0) as f64) {
             cancer.replication /= 2.0;
             let position = transform.translation.truncate()
@@ -324,7 +613,13 @@ pub fn cancer_replication(
                     time.seconds_since_startup().sin() as f32,
                     time.seconds_since_startup().cos() as f32,
                 ) * 4.0;
-            spawn_cancer_cell(&mut commands, position, 0.035, assets.cancer.clone_weak());
+            spawn_cancer_cell(
+                &mut commands,
+                position,
+                0.035,
+                Some(cancer.tumour.unwrap_or(entity)),
+                assets.cancer.clone_weak(),
+            );
             audio.play(
                 audio_assets.pathogen_spawn.clone_weak(),
                 PlaybackSettings {
@@ -337,12 +632,148 @@ pub fn cancer_replication(
     }
 }
 
-fn spawn_cancer_cell(
+pub fn spawn_bacteria(
     commands: &mut Commands,
     position: Vec2,
-    replication: f32,
+    grow_in: Duration,
     texture: Handle<Image>,
-) {
+    resistant: bool,
+) -> Entity {
+    let mut rng = rand::thread_rng();
+    let mut velocity = RigidBodyVelocity::zero();
+    velocity.angvel = rng.gen_range(-0.5..0.5);
+    commands
+        .spawn_bundle(PathogenBundle {
+            sprite: SpriteBundle {
+                transform: Transform {
+                    translation: position.extend(z_layers::PATHOGEN),
+                    scale: Vec2::ZERO.extend(1.0),
+                    rotation: Quat::IDENTITY,
+                },
+                sprite: Sprite {
+                    color: Color::WHITE,
+                    flip_x: rng.gen_bool(0.5),
+                    flip_y: rng.gen_bool(0.5),
+                    custom_size: None,
+                },
+                texture,
+                ..Default::default()
+            },
+            rigid_body: RigidBodyBundle {
+                position: position.into(),
+                damping: RigidBodyDamping {
+                    linear_damping: 15.0,
+                    angular_damping: 0.0,
+                }
+                .into(),
+                velocity: velocity.into(),
+                ..Default::default()
+            },
+            collider: ColliderBundle {
+                shape: ColliderShape::ball(8.0).into(),
+                flags: ColliderFlags {
+                    solver_groups: InteractionGroups::new(1, 1),
+                    ..Default::default()
+                }
+                .into(),
+                ..Default::default()
+            },
+            position_sync: RigidBodyPositionSync::Discrete,
+            pathogen_spec: Bacteria {
+                division: Timer::from_seconds(15.0, true),
+            },
+            pathogen: Pathogen::new(PathogenKind::Bacteria, 50.0, 10.0).with_resistance(resistant),
+            steering: Steering::STALK,
+            tag: ScreenTag,
+            easing: Transform {
+                translation: position.extend(z_layers::PATHOGEN),
+                scale: Vec2::ZERO.extend(1.0),
+                rotation: Quat::IDENTITY,
+            }
+            .ease_to(
+                Transform {
+                    translation: position.extend(z_layers::PATHOGEN),
+                    scale: Vec3::ONE,
+                    rotation: Quat::IDENTITY,
+                },
+                EaseFunction::CubicOut,
+                EasingType::Once { duration: grow_in },
+            ),
+        })
+        .id()
+}
+
+pub fn spawn_virus(
+    commands: &mut Commands,
+    position: Vec2,
+    texture: Handle<Image>,
+    resistant: bool,
+) -> Entity {
+    let mut rng = rand::thread_rng();
+    let mut velocity = RigidBodyVelocity::zero();
+    velocity.angvel = rng.gen_range(-1.5..1.5);
+    commands
+        .spawn_bundle(PathogenBundle {
+            sprite: SpriteBundle {
+                transform: Transform {
+                    translation: position.extend(z_layers::PATHOGEN),
+                    scale: Vec2::ZERO.extend(1.0),
+                    rotation: Quat::IDENTITY,
+                },
+                sprite: Sprite {
+                    color: Color::WHITE,
+                    flip_x: rng.gen_bool(0.5),
+                    flip_y: rng.gen_bool(0.5),
+                    custom_size: None,
+                },
+                texture,
+                ..Default::default()
+            },
+            rigid_body: RigidBodyBundle {
+                position: position.into(),
+                damping: RigidBodyDamping {
+                    linear_damping: 15.0,
+                    angular_damping: 0.0,
+                }
+                .into(),
+                velocity: velocity.into(),
+                ..Default::default()
+            },
+            collider: ColliderBundle {
+                shape: ColliderShape::ball(5.0).into(),
+                flags: ColliderFlags {
+                    solver_groups: InteractionGroups::new(1, 1),
+                    ..Default::default()
+                }
+                .into(),
+                ..Default::default()
+            },
+            position_sync: RigidBodyPositionSync::Discrete,
+            pathogen_spec: Virus,
+            pathogen: Pathogen::new(PathogenKind::Virus, 75.0, 2.0).with_resistance(resistant),
+            steering: Steering::SWARM,
+            tag: ScreenTag,
+            easing: Transform {
+                translation: position.extend(z_layers::PATHOGEN),
+                scale: Vec2::ZERO.extend(1.0),
+                rotation: Quat::IDENTITY,
+            }
+            .ease_to(
+                Transform {
+                    translation: position.extend(z_layers::PATHOGEN),
+                    scale: Vec3::ONE,
+                    rotation: Quat::IDENTITY,
+                },
+                EaseFunction::CubicOut,
+                EasingType::Once {
+                    duration: Duration::from_millis(4000),
+                },
+            ),
+        })
+        .id()
+}
+
+pub fn spawn_fungal_spore(commands: &mut Commands, position: Vec2, texture: Handle<Image>) {
     let mut rng = rand::thread_rng();
     commands.spawn_bundle(PathogenBundle {
         sprite: SpriteBundle {
@@ -368,7 +799,7 @@ fn spawn_cancer_cell(
         },
         collider: ColliderBundle {
             mass_properties: ColliderMassProps::Density(100.0).into(),
-            shape: ColliderShape::ball(9.0).into(),
+            shape: ColliderShape::ball(7.0).into(),
             flags: ColliderFlags {
                 solver_groups: InteractionGroups::new(2, 2),
                 ..Default::default()
@@ -377,13 +808,9 @@ fn spawn_cancer_cell(
             ..Default::default()
         },
         position_sync: RigidBodyPositionSync::Discrete,
-        pathogen_spec: Cancer { replication },
-        pathogen: Pathogen {
-            speed: -1000.0,
-            strength: 1000.0,
-            last_hit: Timer::from_seconds(1.0, true),
-            in_contact: false,
-        },
+        pathogen_spec: Fungus { zone: 0.0 },
+        pathogen: Pathogen::new(PathogenKind::Fungus, 0.0, 5.0),
+        steering: Steering::DIRECT,
         tag: ScreenTag,
         easing: Transform {
             translation: position.extend(z_layers::CANCER),
@@ -398,12 +825,81 @@ fn spawn_cancer_cell(
             },
             EaseFunction::CubicOut,
             EasingType::Once {
-                duration: Duration::from_millis(2000),
+                duration: Duration::from_millis(3000),
             },
         ),
     });
 }
 
+pub fn spawn_cancer_cell(
+    commands: &mut Commands,
+    position: Vec2,
+    replication: f32,
+    tumour: Option<Entity>,
+    texture: Handle<Image>,
+) -> Entity {
+    let mut rng = rand::thread_rng();
+    commands
+        .spawn_bundle(PathogenBundle {
+            sprite: SpriteBundle {
+                transform: Transform::from_translation(position.extend(z_layers::CANCER)),
+                sprite: Sprite {
+                    color: Color::WHITE,
+                    flip_x: rng.gen_bool(0.5),
+                    flip_y: rng.gen_bool(0.5),
+                    custom_size: None,
+                },
+                texture,
+                ..Default::default()
+            },
+            rigid_body: RigidBodyBundle {
+                position: position.into(),
+                mass_properties: RigidBodyMassPropsFlags::ROTATION_LOCKED.into(),
+                damping: RigidBodyDamping {
+                    linear_damping: 200.0,
+                    angular_damping: 200.0,
+                }
+                .into(),
+                ..Default::default()
+            },
+            collider: ColliderBundle {
+                mass_properties: ColliderMassProps::Density(100.0).into(),
+                shape: ColliderShape::ball(9.0).into(),
+                flags: ColliderFlags {
+                    solver_groups: InteractionGroups::new(2, 2),
+                    ..Default::default()
+                }
+                .into(),
+                ..Default::default()
+            },
+            position_sync: RigidBodyPositionSync::Discrete,
+            pathogen_spec: Cancer {
+                replication,
+                tumour,
+            },
+            pathogen: Pathogen::new(PathogenKind::Cancer, -1000.0, 1000.0),
+            steering: Steering::DIRECT,
+            tag: ScreenTag,
+            easing: Transform {
+                translation: position.extend(z_layers::CANCER),
+                scale: Vec2::ZERO.extend(1.0),
+                rotation: Quat::IDENTITY,
+            }
+            .ease_to(
+                Transform {
+                    translation: position.extend(z_layers::CANCER),
+                    scale: Vec3::ONE,
+                    rotation: Quat::IDENTITY,
+                },
+                EaseFunction::CubicOut,
+                EasingType::Once {
+                    duration: Duration::from_millis(2000),
+                },
+            ),
+        })
+        .id()
+}
+
 #[derive(Bundle)]
 struct PathogenBundle<T: 'static + Sync + Send + Component> {
     #[bundle]
@@ -416,5 +912,6 @@ struct PathogenBundle<T: 'static + Sync + Send + Component> {
     tag: ScreenTag,
     position_sync: RigidBodyPositionSync,
     pathogen: Pathogen,
+    steering: Steering,
     easing: EasingComponent<Transform>,
 }
@@ -0,0 +1,349 @@
+use std::fmt;
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_egui::{
+    egui::{self, Color32, RichText},
+    EguiContext,
+};
+use bevy_rapier2d::prelude::*;
+use strum::{EnumIter, IntoEnumIterator};
+
+use crate::assets::InGameAssets;
+
+use super::{
+    arena,
+    director::Director,
+    entry_points::{EntryKind, SpawnWarning},
+    immune_system::ImmuneSystem,
+    parasites::{Parasite, ParasiteSegment},
+    pathogens::{spawn_cancer_cell, spawn_incoming, Cancer, Incoming, Pathogen, PathogenKind},
+    toxins::{Toxic, ToxinCloud},
+    white_cells::{Anchor, WhiteCell},
+    z_layers, HostState, ScreenTag,
+};
+
+const EXIT_RADIUS: f32 = 25.0;
+const BREATH_PERIOD: f32 = 6.0;
+const BREATH_STRENGTH: f32 = 60.0;
+const TRAVEL_COST: f32 = 0.1;
+const EXIT_COOLDOWN: f32 = 30.0;
+
+#[derive(EnumIter, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Region {
+    Skin,
+    Lungs,
+    Gut,
+    Brain,
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Region::Skin => write!(f, "Skin"),
+            Region::Lungs => write!(f, "Lungs"),
+            Region::Gut => write!(f, "Gut"),
+            Region::Brain => write!(f, "Brain"),
+        }
+    }
+}
+
+impl Default for Region {
+    fn default() -> Self {
+        Region::Skin
+    }
+}
+
+impl Region {
+    // rgb: healthy tissue color, w: cell scale
+    pub fn tissue(self) -> Vec4 {
+        match self {
+            Region::Skin => Vec4::new(1.0, 0.08, 0.52, 1.0),
+            Region::Lungs => Vec4::new(1.0, 0.45, 0.55, 0.7),
+            Region::Gut => Vec4::new(0.85, 0.35, 0.2, 1.3),
+            Region::Brain => Vec4::new(0.9, 0.6, 0.65, 0.5),
+        }
+    }
+
+    pub fn mix(self, kind: PathogenKind) -> f32 {
+        match (self, kind) {
+            (Region::Skin, PathogenKind::Bacteria) => 1.5,
+            (Region::Lungs, PathogenKind::Virus) => 2.0,
+            (Region::Lungs, PathogenKind::Fungus) => 2.0,
+            (Region::Gut, PathogenKind::Bacteria) => 2.5,
+            (Region::Brain, PathogenKind::Cancer) => 2.0,
+            (Region::Brain, _) => 0.5,
+            _ => 1.0,
+        }
+    }
+
+    pub fn entry_weight(self, kind: EntryKind) -> f32 {
+        match (self, kind) {
+            (Region::Skin, EntryKind::Wound)
+            | (Region::Lungs, EntryKind::Airway)
+            | (Region::Gut, EntryKind::GutWall) => 3.0,
+            _ => 1.0,
+        }
+    }
+
+    pub fn toxic_chance(self) -> f64 {
+        match self {
+            Region::Gut => 0.4,
+            _ => 0.2,
+        }
+    }
+
+    pub fn inflammation(self) -> f32 {
+        match self {
+            // there is no room for swelling inside the skull
+            Region::Brain => 2.0,
+            _ => 1.0,
+        }
+    }
+
+    pub fn exp_multiplier(self) -> f32 {
+        match self {
+            Region::Skin => 1.0,
+            Region::Lungs => 1.25,
+            Region::Gut => 1.5,
+            Region::Brain => 2.0,
+        }
+    }
+
+    fn color(self) -> Color {
+        let tissue = self.tissue();
+        Color::rgba(tissue.x, tissue.y, tissue.z, 0.8)
+    }
+}
+
+#[derive(Component)]
+pub struct Exit {
+    destination: Region,
+    // vessels contract after a passage and need time to reopen
+    closed: Timer,
+}
+
+impl Exit {
+    fn is_open(&self) -> bool {
+        self.closed.elapsed() >= self.closed.duration()
+    }
+}
+
+// a pathogen left behind in a region, waiting for the immune system to come back
+struct Dormant {
+    incoming: Incoming,
+    position: Vec2,
+    // cancer cells keep their tumour and how fast they still replicate
+    cancer: Option<(Entity, f32)>,
+}
+
+#[derive(Default)]
+pub struct Elsewhere(HashMap<Region, Vec<(Entity, Dormant)>>);
+
+pub fn setup(mut commands: Commands, windows: Res<Windows>, assets: Res<InGameAssets>) {
+    commands.insert_resource(Elsewhere::default());
+    spawn_exits(&mut commands, &windows, Region::default(), 0.0, &assets);
+}
+
+// pathogens don't wait idle, they come back healed
+fn awaken(commands: &mut Commands, dormant: Vec<(Entity, Dormant)>, assets: &InGameAssets) {
+    let mut tumours = HashMap::default();
+    // tumour roots first so the rest of their cells can join them
+    let (roots, others): (Vec<_>, Vec<_>) = dormant.into_iter().partition(
+        |(entity, dormant)| matches!(dormant.cancer, Some((tumour, _)) if tumour == *entity),
+    );
+    for (entity, dormant) in roots.into_iter().chain(others) {
+        match dormant.cancer {
+            Some((tumour, replication)) => {
+                let root = tumours.get(&tumour).copied();
+                let cell = spawn_cancer_cell(
+                    commands,
+                    dormant.position,
+                    replication,
+                    root,
+                    assets.cancer.clone_weak(),
+                );
+                // a tumour that lost its first cell regroups around another one
+                if root.is_none() {
+                    tumours.insert(tumour, cell);
+                }
+            }
+            None => spawn_incoming(commands, dormant.incoming, dormant.position, assets),
+        }
+    }
+}
+
+fn spawn_exits(
+    commands: &mut Commands,
+    windows: &Windows,
+    region: Region,
+    closed_for: f32,
+    assets: &InGameAssets,
+) {
+    let window = windows.get_primary().unwrap();
+    let (width, height) = arena::size(window);
+    let positions = [
+        Vec2::new(-width * 0.47, 0.0),
+        Vec2::new(width * 0.47, 0.0),
+        Vec2::new(0.0, -height * 0.45),
+    ];
+    for (destination, position) in Region::iter()
+        .filter(|destination| *destination != region)
+        .zip(positions)
+    {
+        commands
+            .spawn_bundle(SpriteBundle {
+                transform: Transform::from_translation(position.extend(z_layers::ENTRY_POINT)),
+                sprite: Sprite {
+                    color: destination.color(),
+                    custom_size: Some(Vec2::splat(EXIT_RADIUS * 2.0)),
+                    ..Default::default()
+                },
+                texture: assets.vessel_exit.clone_weak(),
+                ..Default::default()
+            })
+            .insert(Exit {
+                destination,
+                closed: Timer::from_seconds(closed_for, false),
+            })
+            .insert(ScreenTag);
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn travel(
+    mut commands: Commands,
+    mut host_state: ResMut<HostState>,
+    mut director: ResMut<Director>,
+    time: Res<Time>,
+    mut immune_system: Query<(&mut RigidBodyPositionComponent, &mut ImmuneSystem)>,
+    mut white_cells: Query<
+        &mut RigidBodyPositionComponent,
+        (With<WhiteCell>, Without<Anchor>, Without<ImmuneSystem>),
+    >,
+    mut exits: Query<(&Transform, &mut Sprite, &mut Exit)>,
+    pathogens: Query<
+        (
+            Entity,
+            &Transform,
+            &Pathogen,
+            Option<&Toxic>,
+            Option<&Cancer>,
+        ),
+        (Without<Parasite>, Without<ParasiteSegment>),
+    >,
+    left_behind: Query<Entity, Or<(With<Exit>, With<ToxinCloud>, With<SpawnWarning>)>>,
+    mut elsewhere: ResMut<Elsewhere>,
+    windows: Res<Windows>,
+    assets: Res<InGameAssets>,
+) {
+    let (mut rb_position, mut immune_system) = immune_system.single_mut();
+    let position: Vec2 = rb_position.position.translation.into();
+    let mut destination = None;
+    for (transform, mut sprite, mut exit) in exits.iter_mut() {
+        exit.closed.tick(time.delta());
+        let mut color = exit.destination.color();
+        if !exit.is_open() {
+            color.set_a(0.2);
+        }
+        sprite.color = color;
+        if exit.is_open()
+            && transform.translation.truncate().distance_squared(position) < EXIT_RADIUS.powi(2)
+        {
+            destination = Some(exit.destination);
+        }
+    }
+    if let Some(destination) = destination {
+        // squeezing through the vessel wall takes its toll
+        immune_system.health -= immune_system.original_health * TRAVEL_COST;
+        let mut dormant = vec![];
+        for (entity, transform, pathogen, toxic, cancer) in pathogens.iter() {
+            commands.entity(entity).despawn_recursive();
+            dormant.push((
+                entity,
+                Dormant {
+                    incoming: Incoming {
+                        kind: pathogen.kind,
+                        toxic: toxic.is_some(),
+                        resistant: pathogen.resistant,
+                    },
+                    position: transform.translation.truncate(),
+                    // metastatic cells settle where they were and start a new tumour
+                    cancer: (pathogen.kind == PathogenKind::Cancer).then(|| {
+                        cancer.map_or((entity, 0.12), |cancer| {
+                            (cancer.tumour.unwrap_or(entity), cancer.replication)
+                        })
+                    }),
+                },
+            ));
+        }
+        elsewhere.0.insert(host_state.region, dormant);
+        host_state.region = destination;
+        for entity in left_behind.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        if let Some(dormant) = elsewhere.0.remove(&destination) {
+            awaken(&mut commands, dormant, &assets);
+        }
+        rb_position.position.translation.x = 0.0;
+        rb_position.position.translation.y = 0.0;
+        // white cells escort the immune system, structures belong to the body and stay put
+        for mut rb_position in white_cells.iter_mut() {
+            rb_position.position.translation.x -= position.x;
+            rb_position.position.translation.y -= position.y;
+        }
+        spawn_exits(&mut commands, &windows, destination, EXIT_COOLDOWN, &assets);
+        director.announce(format!("Entering the {}", destination));
+    }
+}
+
+pub fn breathing(
+    time: Res<Time>,
+    host_state: Res<HostState>,
+    mut bodies: Query<&mut RigidBodyVelocityComponent, Or<(With<Pathogen>, With<ImmuneSystem>)>>,
+) {
+    if host_state.region != Region::Lungs {
+        return;
+    }
+    let phase = time.seconds_since_startup() as f32 / BREATH_PERIOD * std::f32::consts::TAU;
+    // inhale pulls everything up, exhale pushes it back down
+    let breath = Vec2::new(0.0, phase.sin() * BREATH_STRENGTH * time.delta_seconds());
+    for mut rb_velocity in bodies.iter_mut() {
+        let velocity: Vec2 = rb_velocity.linvel.into();
+        rb_velocity.linvel = (velocity + breath).into();
+    }
+}
+
+pub fn labels(
+    mut egui_context: ResMut<EguiContext>,
+    windows: Res<Windows>,
+    camera: Query<&Transform, With<Camera>>,
+    exits: Query<(&Transform, &Exit)>,
+) {
+    let window = windows.get_primary().unwrap();
+    let camera = camera.single().translation.truncate();
+    for (transform, exit) in exits.iter() {
+        let position = transform.translation.truncate() - camera;
+        egui::Area::new(format!("exit-{}", exit.destination))
+            .fixed_pos([
+                position.x + window.width() / 2.0 - EXIT_RADIUS,
+                window.height() / 2.0 - position.y - EXIT_RADIUS - 16.0,
+            ])
+            .interactable(false)
+            .show(egui_context.ctx_mut(), |ui| {
+                let text = if exit.is_open() {
+                    format!(
+                        "{} x{}",
+                        exit.destination,
+                        exit.destination.exp_multiplier()
+                    )
+                } else {
+                    format!(
+                        "{} {:.0}s",
+                        exit.destination,
+                        exit.closed.duration().as_secs_f32() - exit.closed.elapsed_secs()
+                    )
+                };
+                ui.label(RichText::new(text).small().color(Color32::WHITE));
+            });
+    }
+}
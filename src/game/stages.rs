@@ -0,0 +1,268 @@
+use std::fmt;
+
+use bevy::{audio::AudioSink, prelude::*};
+use bevy_egui::{
+    egui::{self, Align2, Color32, RichText},
+    EguiContext,
+};
+
+use crate::{assets::AudioAssets, menu::button, GameState, UxState};
+
+use super::{director::Director, immune_system::ImmuneSystem, HostState, UiStatus};
+
+pub struct LifeStagePlugin;
+
+impl Plugin for LifeStagePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Milestone).with_system(start_cue))
+            .add_system_set(
+                SystemSet::on_update(GameState::Milestone)
+                    .with_system(milestone.after(UiStatus))
+                    .with_system(super::ui::status.label(UiStatus)),
+            )
+            .add_system_set(SystemSet::on_exit(GameState::Milestone).with_system(stop_cue));
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LifeStage {
+    Infancy,
+    Childhood,
+    Adulthood,
+    OldAge,
+}
+
+impl fmt::Display for LifeStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifeStage::Infancy => f.pad("Infancy"),
+            LifeStage::Childhood => f.pad("Childhood"),
+            LifeStage::Adulthood => f.pad("Adulthood"),
+            LifeStage::OldAge => f.pad("Old Age"),
+        }
+    }
+}
+
+impl LifeStage {
+    fn at(age: f32) -> Self {
+        match age as u32 {
+            0..=29 => LifeStage::Infancy,
+            30..=79 => LifeStage::Childhood,
+            80..=199 => LifeStage::Adulthood,
+            _ => LifeStage::OldAge,
+        }
+    }
+
+    pub fn speed(self) -> f32 {
+        match self {
+            LifeStage::Infancy => 0.8,
+            LifeStage::Childhood => 1.1,
+            LifeStage::Adulthood => 1.0,
+            LifeStage::OldAge => 0.85,
+        }
+    }
+
+    pub fn regen(self) -> f32 {
+        match self {
+            LifeStage::Infancy => 1.3,
+            LifeStage::Childhood => 1.2,
+            LifeStage::Adulthood => 1.0,
+            LifeStage::OldAge => 0.7,
+        }
+    }
+
+    pub fn response(self) -> f32 {
+        match self {
+            // still learning to fight
+            LifeStage::Infancy => 0.7,
+            LifeStage::Childhood => 0.9,
+            LifeStage::Adulthood => 1.1,
+            LifeStage::OldAge => 0.9,
+        }
+    }
+
+    // x: saturation, y: brightness
+    fn grading(self) -> Vec2 {
+        match self {
+            LifeStage::Infancy => Vec2::new(1.0, 1.15),
+            LifeStage::Childhood => Vec2::new(1.0, 1.05),
+            LifeStage::Adulthood => Vec2::new(0.9, 1.0),
+            LifeStage::OldAge => Vec2::new(0.55, 0.85),
+        }
+    }
+
+    pub fn grade(self, tissue: Vec4) -> Vec4 {
+        let grading = self.grading();
+        let color = tissue.truncate();
+        let grey = Vec3::splat((color.x + color.y + color.z) / 3.0);
+        (grey.lerp(color, grading.x) * grading.y).extend(tissue.w)
+    }
+
+    // tempo of the background loop while the milestone is open
+    fn cue(self) -> f32 {
+        match self {
+            LifeStage::Infancy => 1.3,
+            LifeStage::Childhood => 1.15,
+            LifeStage::Adulthood => 1.0,
+            LifeStage::OldAge => 0.75,
+        }
+    }
+
+    fn choices(self) -> [Choice; 2] {
+        match self {
+            LifeStage::Infancy | LifeStage::Childhood => [Choice::Agility, Choice::Endurance],
+            LifeStage::Adulthood => [Choice::Vigilance, Choice::Rest],
+            LifeStage::OldAge => [Choice::Endurance, Choice::Rest],
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Choice {
+    Agility,
+    Endurance,
+    Vigilance,
+    Rest,
+}
+
+impl fmt::Display for Choice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Choice::Agility => f.pad("Agility"),
+            Choice::Endurance => f.pad("Endurance"),
+            Choice::Vigilance => f.pad("Vigilance"),
+            Choice::Rest => f.pad("Rest"),
+        }
+    }
+}
+
+impl Choice {
+    const fn info(self) -> &'static str {
+        match self {
+            Choice::Agility => "Faster movement, less total health",
+            Choice::Endurance => "More total health, slower movement",
+            Choice::Vigilance => "More white cells, slower healing",
+            Choice::Rest => "Faster healing, weaker white cells",
+        }
+    }
+
+    fn apply(self, immune_system: &mut ImmuneSystem, host: &mut HostState) {
+        match self {
+            Choice::Agility => {
+                immune_system.speed *= 1.25;
+                immune_system.original_health *= 0.85;
+                immune_system.health = immune_system.health.min(immune_system.original_health);
+            }
+            Choice::Endurance => {
+                immune_system.original_health *= 1.25;
+                immune_system.speed *= 0.85;
+            }
+            Choice::Vigilance => {
+                immune_system.attack_spawn_rate += 0.5;
+                // additive so it still matters for a host without any regen yet
+                host.regen = (host.regen - 0.15).max(0.0);
+            }
+            Choice::Rest => {
+                host.regen += 0.3;
+                immune_system.attack_damage *= 0.9;
+            }
+        }
+    }
+}
+
+pub fn grow_up(
+    mut host_state: ResMut<HostState>,
+    mut director: ResMut<Director>,
+    mut state: ResMut<State<GameState>>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+) {
+    let stage = LifeStage::at(host_state.age);
+    if stage != host_state.stage && state.push(GameState::Milestone).is_ok() {
+        host_state.stage = stage;
+        director.announce(format!("{}", stage));
+        audio.play(
+            audio_assets.won.clone_weak(),
+            PlaybackSettings {
+                repeat: false,
+                speed: 1.0,
+                volume: 0.2,
+            },
+        );
+    }
+}
+
+fn start_cue(
+    host: Res<HostState>,
+    mut ux: ResMut<UxState>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+    audio_sinks: Res<Assets<AudioSink>>,
+) {
+    let sink = audio.play(
+        audio_assets.background_loop.clone_weak(),
+        PlaybackSettings {
+            repeat: true,
+            volume: 0.05,
+            speed: host.stage.cue(),
+        },
+    );
+    ux.background_loop = Some(audio_sinks.get_handle(sink));
+}
+
+fn stop_cue(mut ux: ResMut<UxState>, audio_sinks: Res<Assets<AudioSink>>) {
+    if let Some(sink) = ux
+        .background_loop
+        .take()
+        .and_then(|sink| audio_sinks.get(sink))
+    {
+        sink.stop();
+    }
+}
+
+fn milestone(
+    mut egui_context: ResMut<EguiContext>,
+    mut immune_system: Query<&mut ImmuneSystem>,
+    mut host: ResMut<HostState>,
+    mut state: ResMut<State<GameState>>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<Audio>,
+) {
+    let stage = host.stage;
+    egui::Window::new(RichText::new(format!("{}", stage)).color(Color32::RED))
+        .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .min_width(800.0)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.vertical_centered(|ui| {
+                ui.label("A new stage of life. How will you spend it?");
+                ui.vertical_centered_justified(|ui| {
+                    ui.set_max_width(350.0);
+                    for choice in stage.choices() {
+                        ui.add_space(20.0);
+                        button(
+                            ui,
+                            format!("{}", choice),
+                            || {
+                                choice.apply(&mut immune_system.single_mut(), &mut host);
+                                audio.play(
+                                    audio_assets.button.clone_weak(),
+                                    PlaybackSettings {
+                                        repeat: false,
+                                        speed: 1.0,
+                                        volume: 0.2,
+                                    },
+                                );
+                                let _ = state.pop();
+                            },
+                            true,
+                            false,
+                        );
+                        ui.small(choice.info());
+                    }
+                    ui.add_space(10.0);
+                });
+            });
+        });
+}
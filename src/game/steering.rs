@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+
+use super::pathogens::PathogenKind;
+
+const NEIGHBOUR_RADIUS: f32 = 60.0;
+const SEPARATION_RADIUS: f32 = 20.0;
+const FLEE_RADIUS: f32 = 50.0;
+const FLANKING_DISTANCE: f32 = 80.0;
+const COLONY_RADIUS: f32 = 40.0;
+
+pub struct Neighbour {
+    pub entity: Entity,
+    pub kind: PathogenKind,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+#[derive(Component, Clone, Copy)]
+pub struct Steering {
+    seek: f32,
+    separation: f32,
+    alignment: f32,
+    cohesion: f32,
+    // tight pull toward the centre of nearby cells of the same kind
+    colony: f32,
+    flanking: f32,
+    flee: f32,
+}
+
+impl Steering {
+    // move toward the immune system and nothing else
+    pub const DIRECT: Steering = Steering {
+        seek: 1.0,
+        separation: 0.0,
+        alignment: 0.0,
+        cohesion: 0.0,
+        colony: 0.0,
+        flanking: 0.0,
+        flee: 0.0,
+    };
+    // fly in tight swarms
+    pub const SWARM: Steering = Steering {
+        seek: 0.8,
+        separation: 0.6,
+        alignment: 0.8,
+        cohesion: 0.6,
+        colony: 0.0,
+        flanking: 0.0,
+        flee: 0.2,
+    };
+    // keep together and wait for an opening in the white cells
+    pub const STALK: Steering = Steering {
+        seek: 0.7,
+        separation: 0.4,
+        alignment: 0.0,
+        cohesion: 1.0,
+        colony: 0.8,
+        flanking: 0.0,
+        flee: 1.2,
+    };
+    // stay between the immune system and the blood vessel
+    pub const FLANK: Steering = Steering {
+        seek: 0.3,
+        separation: 0.5,
+        alignment: 0.0,
+        cohesion: 0.2,
+        colony: 0.0,
+        flanking: 1.2,
+        flee: 0.6,
+    };
+
+    pub fn direction(
+        &self,
+        me: &Neighbour,
+        target: Vec2,
+        vessel: Vec2,
+        neighbours: &[Neighbour],
+        threats: &[Vec2],
+    ) -> Vec2 {
+        let seek = (target - me.position).normalize_or_zero();
+
+        let flank_at = target + (vessel - target).normalize_or_zero() * FLANKING_DISTANCE;
+        let flanking = (flank_at - me.position).normalize_or_zero();
+
+        let mut separation = Vec2::ZERO;
+        let mut velocities = Vec2::ZERO;
+        let mut positions = Vec2::ZERO;
+        let mut flock = 0;
+        let mut colony_positions = Vec2::ZERO;
+        let mut colony_size = 0;
+        for other in neighbours.iter().filter(|other| other.entity != me.entity) {
+            let offset = me.position - other.position;
+            let distance_squared = offset.length_squared();
+            if distance_squared < SEPARATION_RADIUS.powi(2) {
+                separation += offset / distance_squared.max(1.0);
+            }
+            if other.kind == me.kind && distance_squared < NEIGHBOUR_RADIUS.powi(2) {
+                velocities += other.velocity;
+                positions += other.position;
+                flock += 1;
+            }
+            if other.kind == me.kind && distance_squared < COLONY_RADIUS.powi(2) {
+                colony_positions += other.position;
+                colony_size += 1;
+            }
+        }
+        let (alignment, cohesion) = if flock > 0 {
+            (
+                velocities.normalize_or_zero(),
+                (positions / flock as f32 - me.position).normalize_or_zero(),
+            )
+        } else {
+            (Vec2::ZERO, Vec2::ZERO)
+        };
+
+        let colony = if colony_size > 0 {
+            (colony_positions / colony_size as f32 - me.position).normalize_or_zero()
+        } else {
+            Vec2::ZERO
+        };
+
+        let flee = threats
+            .iter()
+            .map(|threat| me.position - *threat)
+            .filter(|offset| offset.length_squared() < FLEE_RADIUS.powi(2))
+            .map(|offset| offset.normalize_or_zero() * (1.0 - offset.length() / FLEE_RADIUS))
+            .fold(Vec2::ZERO, |flee, away| flee + away)
+            .normalize_or_zero();
+
+        (seek * self.seek
+            + separation.normalize_or_zero() * self.separation
+            + alignment * self.alignment
+            + cohesion * self.cohesion
+            + colony * self.colony
+            + flanking * self.flanking
+            + flee * self.flee)
+            // weights only pick the heading, every archetype moves at its own full speed
+            .normalize_or_zero()
+    }
+}
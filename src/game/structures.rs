@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::assets::InGameAssets;
+
+use super::{
+    arena,
+    immune_system::{spawn_white_cell_at, ImmuneSystem},
+    pathogens::Pathogen,
+    white_cells::Anchor,
+    z_layers, HostState, ScreenTag,
+};
+
+const STRUCTURE_RADIUS: f32 = 24.0;
+const VISIT_RADIUS: f32 = 40.0;
+const PATHOGEN_DAMAGE: f32 = 2.0;
+const REPAIR_RATE: f32 = 5.0;
+const LYMPH_SPAWN_RATE: f32 = 0.5;
+const MARROW_REGEN: f32 = 0.3;
+// a node stops producing once this many of its cells are still guarding it
+const LYMPH_GARRISON: usize = 4;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StructureKind {
+    LymphNode,
+    BoneMarrow,
+}
+
+impl StructureKind {
+    const fn health(self) -> f32 {
+        match self {
+            StructureKind::LymphNode => 60.0,
+            StructureKind::BoneMarrow => 100.0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Structure {
+    pub kind: StructureKind,
+    pub health: f32,
+    original_health: f32,
+    pub active: bool,
+    // brought down by pathogens, stays inactive until fully repaired
+    pub destroyed: bool,
+}
+
+pub fn setup(mut commands: Commands, windows: Res<Windows>, assets: Res<InGameAssets>) {
+    let window = windows.get_primary().unwrap();
+    let (width, height) = arena::size(window);
+    for (kind, position) in [
+        (
+            StructureKind::LymphNode,
+            Vec2::new(-width * 0.22, height * 0.05),
+        ),
+        (
+            StructureKind::LymphNode,
+            Vec2::new(width * 0.22, -height * 0.1),
+        ),
+        (StructureKind::BoneMarrow, Vec2::new(0.0, -height * 0.3)),
+    ] {
+        commands
+            .spawn_bundle(SpriteBundle {
+                transform: Transform::from_translation(position.extend(z_layers::STRUCTURE)),
+                sprite: Sprite {
+                    color: Color::GRAY,
+                    custom_size: Some(Vec2::splat(STRUCTURE_RADIUS * 2.0)),
+                    ..Default::default()
+                },
+                texture: match kind {
+                    StructureKind::LymphNode => assets.lymph_node.clone_weak(),
+                    StructureKind::BoneMarrow => assets.bone_marrow.clone_weak(),
+                },
+                ..Default::default()
+            })
+            .insert(Structure {
+                kind,
+                health: kind.health(),
+                original_health: kind.health(),
+                active: false,
+                destroyed: false,
+            })
+            .insert(ScreenTag);
+    }
+}
+
+pub fn visit(
+    time: Res<Time>,
+    immune_system: Query<&Transform, With<ImmuneSystem>>,
+    mut structures: Query<(&Transform, &mut Structure)>,
+) {
+    let position = immune_system.single().translation.truncate();
+    for (transform, mut structure) in structures.iter_mut() {
+        if transform.translation.truncate().distance_squared(position) < VISIT_RADIUS.powi(2) {
+            structure.health = (structure.health + REPAIR_RATE * time.delta_seconds())
+                .min(structure.original_health);
+            if structure.health >= structure.original_health {
+                structure.destroyed = false;
+            }
+            structure.active = !structure.destroyed;
+        }
+    }
+}
+
+pub fn attacked(
+    time: Res<Time>,
+    pathogens: Query<&Transform, With<Pathogen>>,
+    mut structures: Query<(&Transform, &mut Structure)>,
+) {
+    for (transform, mut structure) in structures.iter_mut() {
+        let position = transform.translation.truncate();
+        let attackers = pathogens
+            .iter()
+            .filter(|pathogen| {
+                pathogen.translation.truncate().distance_squared(position)
+                    < STRUCTURE_RADIUS.powi(2)
+            })
+            .count();
+        if attackers == 0 {
+            continue;
+        }
+        structure.health -= attackers as f32 * PATHOGEN_DAMAGE * time.delta_seconds();
+        if structure.health <= 0.0 {
+            structure.health = 0.0;
+            structure.active = false;
+            structure.destroyed = true;
+        }
+    }
+}
+
+pub fn lymph_nodes(
+    mut commands: Commands,
+    time: Res<Time>,
+    structures: Query<(&Transform, &Structure)>,
+    immune_system: Query<&ImmuneSystem>,
+    anchored: Query<&Anchor>,
+    assets: Res<InGameAssets>,
+) {
+    let immune_system = immune_system.single();
+    let mut rng = rand::thread_rng();
+    for (transform, _) in structures
+        .iter()
+        .filter(|(_, structure)| structure.active && structure.kind == StructureKind::LymphNode)
+    {
+        let position = transform.translation.truncate();
+        let garrison = anchored
+            .iter()
+            .filter(|Anchor(node)| *node == position)
+            .count();
+        if garrison < LYMPH_GARRISON
+            && rng.gen_bool(
+                ((immune_system.attack_spawn_rate + 0.2) * LYMPH_SPAWN_RATE * time.delta_seconds())
+                    .clamp(0.0, 1.0) as f64,
+            )
+        {
+            let kind = *immune_system
+                .cell_types
+                .choose_weighted(&mut rng, |kind| kind.weight())
+                .unwrap();
+            let white_cell = spawn_white_cell_at(
+                &mut commands,
+                position,
+                kind,
+                time.seconds_since_startup() as f32,
+                immune_system.attack_damage,
+                &assets,
+            );
+            commands.entity(white_cell).insert(Anchor(position));
+        }
+    }
+}
+
+pub fn bone_marrow(
+    time: Res<Time>,
+    host_state: Res<HostState>,
+    structures: Query<&Structure>,
+    mut immune_system: Query<&mut ImmuneSystem>,
+) {
+    let mut immune_system = immune_system.single_mut();
+    for structure in structures
+        .iter()
+        .filter(|structure| structure.active && structure.kind == StructureKind::BoneMarrow)
+    {
+        immune_system.health = (immune_system.health
+            + MARROW_REGEN
+                * host_state.regen.max(1.0)
+                * (structure.health / structure.original_health)
+                * time.delta_seconds())
+        .min(immune_system.original_health);
+    }
+}
+
+pub fn appearance(mut structures: Query<(&Structure, &mut Sprite)>) {
+    for (structure, mut sprite) in structures.iter_mut() {
+        let health = structure.health / structure.original_health;
+        sprite.color = if structure.active {
+            Color::rgb(1.0, health, health)
+        } else if structure.destroyed {
+            // darkened while being rebuilt
+            Color::rgb(0.2 + 0.3 * health, 0.2 + 0.3 * health, 0.2 + 0.3 * health)
+        } else {
+            Color::GRAY
+        };
+    }
+}
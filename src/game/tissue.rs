@@ -16,11 +16,19 @@ use bevy::{
     },
     sprite::{Material2d, Material2dPipeline, Material2dPlugin, MaterialMesh2dBundle},
 };
-use rand::Rng;
 
-use crate::{game::z_layers, tear_down, GameState};
+use crate::{
+    game::{arena, z_layers},
+    tear_down, GameState,
+};
 
-use super::host::HostState;
+use super::{
+    host::HostState,
+    interventions::Inventory,
+    pathogens::{Fungus, MAX_FUNGAL_ZONES},
+    regions::Region,
+    vessel::{Vessels, FLOW_SPEED, MAX_SEGMENTS},
+};
 
 #[derive(Component)]
 pub struct ScreenTag;
@@ -49,8 +57,8 @@ fn setup(
 ) {
     debug!("Loading Tissue");
 
-    let window = windows.get_primary().unwrap();
-    let resolution = Vec2::new(window.width(), window.height());
+    let (width, height) = arena::size(windows.get_primary().unwrap());
+    let resolution = Vec2::new(width, height);
 
     commands
         .spawn_bundle(MaterialMesh2dBundle {
@@ -63,9 +71,14 @@ fn setup(
             material: materials.add(TissueMaterial {
                 resolution,
                 time: 0.0,
-                seed: rand::thread_rng().gen::<i16>() as f32,
+                // replaced by the run seed once the host is set up
+                seed: 0.0,
                 sickness: 0.0,
-                dilatation: 500.0,
+                flow_speed: FLOW_SPEED,
+                healthy: Region::default().tissue(),
+                zones: [Vec4::ZERO; MAX_FUNGAL_ZONES],
+                vessels: [Vec4::ZERO; MAX_SEGMENTS],
+                vessel_widths: [Vec4::ZERO; MAX_SEGMENTS],
             }),
             ..Default::default()
         })
@@ -77,12 +90,41 @@ fn update_tissue_material(
     time: Res<Time>,
     mut tissue_materials: ResMut<Assets<TissueMaterial>>,
     host: Option<Res<HostState>>,
+    vessels: Option<Res<Vessels>>,
+    inventory: Option<Res<Inventory>>,
+    fungi: Query<(&Transform, &Fungus)>,
 ) {
     if let Some((_, mut tissue_material)) = tissue_materials.iter_mut().next() {
         let host = host.unwrap();
+        let vessels = vessels.unwrap();
+        let inventory = inventory.unwrap();
         tissue_material.time = time.seconds_since_startup() as f32;
-        tissue_material.sickness = host.sickness;
-        tissue_material.dilatation = host.dilatation;
+        tissue_material.seed = host.seed as i16 as f32;
+        // painkillers only hide how sick the host looks
+        tissue_material.sickness = host.sickness * inventory.sickness_mask();
+        for ((vessel, width), segment) in tissue_material
+            .vessels
+            .iter_mut()
+            .zip(tissue_material.vessel_widths.iter_mut())
+            .zip(vessels.segments.iter())
+        {
+            *vessel = segment.start.extend(segment.end.x).extend(segment.end.y);
+            *width = Vec4::new(
+                segment.width * host.dilatation / 5.0,
+                segment.offset,
+                0.0,
+                0.0,
+            );
+        }
+        tissue_material.healthy = host.stage.grade(host.region.tissue());
+        tissue_material.zones = [Vec4::ZERO; MAX_FUNGAL_ZONES];
+        for (zone, (transform, fungus)) in tissue_material.zones.iter_mut().zip(fungi.iter()) {
+            *zone = transform
+                .translation
+                .truncate()
+                .extend(fungus.zone)
+                .extend(0.0);
+        }
     }
 }
 
@@ -93,7 +135,14 @@ struct TissueMaterial {
     time: f32,
     seed: f32,
     sickness: f32,
-    dilatation: f32,
+    flow_speed: f32,
+    healthy: Vec4,
+    // x, y: center, z: radius
+    zones: [Vec4; MAX_FUNGAL_ZONES],
+    // x, y: start, z, w: end
+    vessels: [Vec4; MAX_SEGMENTS],
+    // x: width, y: distance along the flow at start
+    vessel_widths: [Vec4; MAX_SEGMENTS],
 }
 
 #[derive(Clone)]
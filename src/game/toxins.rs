@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::assets::InGameAssets;
+
+use super::{immune_system::ImmuneSystem, z_layers, ScreenTag};
+
+const CLOUD_DAMAGE: f32 = 1.5;
+const CLOUD_MAX_RADIUS: f32 = 60.0;
+
+#[derive(Component)]
+pub struct Toxic {
+    pub release: Timer,
+}
+
+#[derive(Component)]
+pub struct ToxinCloud {
+    radius: f32,
+    lifetime: Timer,
+    pub in_contact: bool,
+}
+
+pub fn release(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toxic: Query<(&RigidBodyPositionComponent, &mut Toxic)>,
+    assets: Res<InGameAssets>,
+) {
+    for (rb_position, mut toxic) in toxic.iter_mut() {
+        if toxic.release.tick(time.delta()).just_finished() {
+            let position: Vec2 = rb_position.position.translation.into();
+            commands
+                .spawn_bundle(SpriteBundle {
+                    transform: Transform::from_translation(position.extend(z_layers::TOXIN)),
+                    sprite: Sprite {
+                        color: Color::rgba(0.6, 0.9, 0.2, 0.6),
+                        custom_size: Some(Vec2::splat(16.0)),
+                        ..Default::default()
+                    },
+                    texture: assets.toxin_cloud.clone_weak(),
+                    ..Default::default()
+                })
+                .insert_bundle(RigidBodyBundle {
+                    body_type: RigidBodyType::Static.into(),
+                    position: position.into(),
+                    ..Default::default()
+                })
+                .insert_bundle(ColliderBundle {
+                    collider_type: ColliderType::Sensor.into(),
+                    shape: ColliderShape::ball(8.0).into(),
+                    flags: ActiveEvents::INTERSECTION_EVENTS.into(),
+                    ..Default::default()
+                })
+                .insert(ToxinCloud {
+                    radius: 8.0,
+                    lifetime: Timer::from_seconds(4.0, false),
+                    in_contact: false,
+                })
+                .insert(ScreenTag);
+        }
+    }
+}
+
+pub fn spread(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut clouds: Query<(
+        Entity,
+        &mut ToxinCloud,
+        &mut ColliderShapeComponent,
+        &mut Sprite,
+    )>,
+) {
+    for (entity, mut cloud, mut shape, mut sprite) in clouds.iter_mut() {
+        if cloud.lifetime.tick(time.delta()).just_finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        cloud.radius = 8.0 + (CLOUD_MAX_RADIUS - 8.0) * cloud.lifetime.percent();
+        *shape = ColliderShape::ball(cloud.radius).into();
+        sprite.custom_size = Some(Vec2::splat(cloud.radius * 2.0));
+        sprite.color.set_a(0.6 * cloud.lifetime.percent_left());
+    }
+}
+
+pub fn exposure(
+    mut intersection_events: EventReader<IntersectionEvent>,
+    mut clouds: Query<&mut ToxinCloud>,
+    immune_system: Query<&ImmuneSystem>,
+) {
+    for event in intersection_events.iter() {
+        let entity1 = event.collider1.entity();
+        let entity2 = event.collider2.entity();
+        if let Ok(mut cloud) = {
+            if immune_system.contains(entity1) {
+                clouds.get_mut(entity2)
+            } else if immune_system.contains(entity2) {
+                clouds.get_mut(entity1)
+            } else {
+                continue;
+            }
+        } {
+            cloud.in_contact = event.intersecting;
+        }
+    }
+}
+
+pub fn damage(
+    clouds: Query<&ToxinCloud>,
+    mut immune_system: Query<&mut ImmuneSystem>,
+    time: Res<Time>,
+) {
+    let exposed = clouds.iter().filter(|cloud| cloud.in_contact).count();
+    if exposed > 0 {
+        let mut immune_system = immune_system.single_mut();
+        immune_system.health -= CLOUD_DAMAGE * exposed as f32 * time.delta_seconds();
+    }
+}
@@ -0,0 +1,174 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_rapier2d::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::assets::InGameAssets;
+
+use super::{
+    arena,
+    host::HostState,
+    interventions::Inventory,
+    pathogens::{spawn_cancer_cell, Cancer, Pathogen, PathogenKind},
+    vessel::{Vessels, VESSEL_CLEARANCE},
+    z_layers, ScreenTag,
+};
+
+// small tumours evade the immune system
+const EVASIVE_ARMOR: f32 = 1000.0;
+const VULNERABLE_TUMOUR_SIZE: usize = 5;
+const METASTATIC_TUMOUR_SIZE: usize = 3;
+const METASTASIS_RATE: f32 = 0.01;
+const METASTASIS_SPEED: f32 = 40.0;
+
+#[derive(Component)]
+pub struct Metastasis {
+    destination: Vec2,
+    in_vessel: bool,
+}
+
+pub fn tumours(
+    mut commands: Commands,
+    time: Res<Time>,
+    windows: Res<Windows>,
+    host_state: Res<HostState>,
+    vessels: Res<Vessels>,
+    mut cancer_cells: Query<(Entity, &Transform, &Cancer, &mut Pathogen)>,
+    assets: Res<InGameAssets>,
+) {
+    let mut tumours: HashMap<Entity, Vec<Vec2>> = HashMap::default();
+    for (entity, transform, cancer, _) in cancer_cells.iter() {
+        tumours
+            .entry(cancer.tumour.unwrap_or(entity))
+            .or_default()
+            .push(transform.translation.truncate());
+    }
+
+    for (entity, _, cancer, mut pathogen) in cancer_cells.iter_mut() {
+        let size = tumours[&cancer.tumour.unwrap_or(entity)].len();
+        pathogen.armor = if size >= VULNERABLE_TUMOUR_SIZE {
+            PathogenKind::Cancer.armor()
+        } else {
+            EVASIVE_ARMOR
+        };
+    }
+
+    // each tumour sheds on its own, whatever its size
+    let mut rng = rand::thread_rng();
+    for cells in tumours.values() {
+        if cells.len() >= METASTATIC_TUMOUR_SIZE
+            && rng.gen_bool((METASTASIS_RATE * time.delta_seconds()).clamp(0.0, 1.0) as f64)
+        {
+            let window = windows.get_primary().unwrap();
+            let (width, height) = arena::size(window);
+            let origin = *cells.choose(&mut rng).unwrap();
+            let destination = std::iter::repeat_with(|| {
+                Vec2::new(
+                    rng.gen_range((-width / 2.0)..(width / 2.0)),
+                    rng.gen_range((-height / 2.0)..(height / 2.0 * 0.9)),
+                )
+            })
+            .find(|pos| {
+                vessels.nearest(*pos, host_state.dilatation).distance > VESSEL_CLEARANCE
+                    && pos.distance_squared(origin) > 80_000.0
+            })
+            .unwrap();
+            spawn_metastasis(
+                &mut commands,
+                origin,
+                destination,
+                assets.cancer.clone_weak(),
+            );
+        }
+    }
+}
+
+fn spawn_metastasis(
+    commands: &mut Commands,
+    position: Vec2,
+    destination: Vec2,
+    texture: Handle<Image>,
+) {
+    let mut pathogen = Pathogen::new(PathogenKind::Cancer, METASTASIS_SPEED, 5.0);
+    pathogen.health = 30.0;
+    pathogen.original_health = 30.0;
+    pathogen.armor = 0.0;
+    commands
+        .spawn_bundle(SpriteBundle {
+            transform: Transform::from_translation(position.extend(z_layers::PATHOGEN)),
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(10.0)),
+                ..Default::default()
+            },
+            texture,
+            ..Default::default()
+        })
+        .insert_bundle(RigidBodyBundle {
+            position: position.into(),
+            damping: RigidBodyDamping {
+                linear_damping: 15.0,
+                angular_damping: 0.0,
+            }
+            .into(),
+            ..Default::default()
+        })
+        .insert_bundle(ColliderBundle {
+            shape: ColliderShape::ball(4.0).into(),
+            flags: ColliderFlags {
+                solver_groups: InteractionGroups::new(1, 1),
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        })
+        .insert(RigidBodyPositionSync::Discrete)
+        .insert(pathogen)
+        .insert(Metastasis {
+            destination,
+            in_vessel: false,
+        })
+        .insert(ScreenTag);
+}
+
+// metastatic cells travel through the blood vessel before seeding a new tumour
+pub fn metastasis(
+    mut commands: Commands,
+    mut cells: Query<(
+        Entity,
+        &RigidBodyPositionComponent,
+        &mut RigidBodyForcesComponent,
+        &mut Metastasis,
+    )>,
+    host_state: Res<HostState>,
+    inventory: Res<Inventory>,
+    vessels: Res<Vessels>,
+    assets: Res<InGameAssets>,
+) {
+    for (entity, rb_position, mut rb_forces, mut metastasis) in cells.iter_mut() {
+        let position: Vec2 = rb_position.position.translation.into();
+        let vessel = vessels.nearest(position, host_state.dilatation);
+        if vessel.inside() {
+            metastasis.in_vessel = true;
+        }
+        if metastasis.in_vessel
+            && !inventory.hospitalised()
+            && position.distance_squared(metastasis.destination) < 100.0
+        {
+            commands.entity(entity).despawn_recursive();
+            spawn_cancer_cell(
+                &mut commands,
+                metastasis.destination,
+                0.12,
+                None,
+                assets.cancer.clone_weak(),
+            );
+            continue;
+        }
+        let target = if metastasis.in_vessel {
+            metastasis.destination
+        } else {
+            vessel.point
+        };
+        let move_by = (target - position).normalize_or_zero() * METASTASIS_SPEED * 1000.0;
+        rb_forces.force = move_by.into();
+    }
+}
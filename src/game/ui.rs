@@ -6,13 +6,21 @@ use bevy_egui::{
 
 use crate::GlobalState;
 
-use super::{immune_system::ImmuneSystem, HostState};
+use super::{
+    abilities::Abilities, immune_system::ImmuneSystem, interventions::Inventory,
+    lifestyle::Lifestyle, parasites::Parasite, pathogens::Pathogen, HostState,
+};
 
+#[allow(clippy::too_many_arguments)]
 pub fn status(
     mut egui_context: ResMut<EguiContext>,
     state: Res<HostState>,
     global_state: Res<GlobalState>,
     immune_system: Query<&ImmuneSystem>,
+    parasites: Query<(&Pathogen, &Parasite)>,
+    abilities: Res<Abilities>,
+    lifestyle: Res<Lifestyle>,
+    inventory: Res<Inventory>,
     mut healthbar_animation: Local<(Option<Timer>, f32)>,
     time: Res<Time>,
 ) {
@@ -59,12 +67,38 @@ pub fn status(
                 ui.painter().text(
                     rect.center(),
                     Align2::CENTER_CENTER,
-                    &format!("Age: {:.1}", state.age),
+                    &format!("{} - Age: {:.1}", state.region, state.age),
                     TextStyle::Small,
                     Color32::WHITE,
                 );
             }
 
+            {
+                let mut rect = rect;
+                let (left, right) = (
+                    egui::lerp(rect.left()..=rect.right(), 0.46),
+                    egui::lerp(rect.left()..=rect.right(), 0.54),
+                );
+                rect.set_left(left);
+                rect.set_right(right);
+                rect.set_top(egui::lerp(rect.top()..=rect.bottom(), 0.85));
+
+                ui.painter()
+                    .rect(rect, 0.0, Color32::DARK_GRAY, Stroke::none());
+                if state.inflammation > 0.0 {
+                    let mut inflammation_bar = rect;
+                    inflammation_bar
+                        .set_right(egui::lerp(rect.left()..=rect.right(), state.inflammation));
+                    let color = if state.is_inflamed() {
+                        Color32::RED
+                    } else {
+                        Color32::from_rgb(255, 140, 0)
+                    };
+                    ui.painter()
+                        .rect(inflammation_bar, 0.0, color, Stroke::none());
+                }
+            }
+
             {
                 if global_state.expectancy > 0.0 {
                     let mut rect = rect;
@@ -99,4 +133,132 @@ pub fn status(
 
         rect
     });
+
+    if let Some((pathogen, parasite)) = parasites.iter().next() {
+        egui::TopBottomPanel::top("parasite").show(egui_context.ctx_mut(), |ui| -> egui::Rect {
+            let (rect, _) = ui.allocate_exact_size(ui.available_size(), egui::Sense::click());
+            if ui.is_rect_visible(rect) {
+                let radius = 0.2 * rect.height();
+                let mut rect = rect;
+                let (left, right) = (
+                    egui::lerp(rect.left()..=rect.right(), 0.25),
+                    egui::lerp(rect.left()..=rect.right(), 0.75),
+                );
+                rect.set_left(left);
+                rect.set_right(right);
+
+                ui.painter()
+                    .rect(rect, radius, Color32::DARK_GRAY, Stroke::none());
+                let current_health = (pathogen.health / pathogen.original_health).max(0.0);
+                if current_health > 0.0 {
+                    let end = egui::lerp(rect.left()..=rect.right(), current_health);
+                    let mut health_bar = rect;
+                    health_bar.set_right(end);
+                    ui.painter()
+                        .rect(health_bar, radius, Color32::DARK_RED, Stroke::none());
+                }
+                ui.painter().text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    &format!("{}", parasite.species),
+                    TextStyle::Small,
+                    Color32::WHITE,
+                );
+            }
+
+            rect
+        });
+    }
+
+    egui::TopBottomPanel::bottom("abilities").show(egui_context.ctx_mut(), |ui| -> egui::Rect {
+        let (rect, _) = ui.allocate_exact_size(ui.available_size(), egui::Sense::click());
+        if ui.is_rect_visible(rect) {
+            let radius = 0.2 * rect.height();
+            for (i, (ability, ready)) in abilities.ready().enumerate() {
+                let mut rect = rect;
+                let (left, right) = (
+                    egui::lerp(rect.left()..=rect.right(), 0.01 + i as f32 * 0.12),
+                    egui::lerp(rect.left()..=rect.right(), 0.12 + i as f32 * 0.12),
+                );
+                rect.set_left(left);
+                rect.set_right(right);
+
+                ui.painter()
+                    .rect(rect, radius, Color32::DARK_GRAY, Stroke::none());
+                let mut cooldown_bar = rect;
+                cooldown_bar.set_right(egui::lerp(rect.left()..=rect.right(), ready));
+                let color = if ready < 1.0 {
+                    Color32::from_rgb(90, 90, 140)
+                } else {
+                    Color32::from_rgb(60, 140, 220)
+                };
+                ui.painter()
+                    .rect(cooldown_bar, radius, color, Stroke::none());
+                ui.painter().text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    &format!("{}", ability),
+                    TextStyle::Small,
+                    Color32::WHITE,
+                );
+            }
+
+            for (i, (intervention, count, active)) in inventory.slots().enumerate() {
+                let mut rect = rect;
+                let (left, right) = (
+                    egui::lerp(rect.left()..=rect.right(), 0.39 + i as f32 * 0.09),
+                    egui::lerp(rect.left()..=rect.right(), 0.47 + i as f32 * 0.09),
+                );
+                rect.set_left(left);
+                rect.set_right(right);
+
+                ui.painter()
+                    .rect(rect, radius, Color32::DARK_GRAY, Stroke::none());
+                if active > 0.0 {
+                    let mut active_bar = rect;
+                    active_bar.set_right(egui::lerp(rect.left()..=rect.right(), active));
+                    ui.painter().rect(
+                        active_bar,
+                        radius,
+                        Color32::from_rgb(60, 160, 90),
+                        Stroke::none(),
+                    );
+                }
+                ui.painter().text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    &format!("{} {} x{}", intervention.hint(), intervention, count),
+                    TextStyle::Small,
+                    if count > 0 {
+                        Color32::WHITE
+                    } else {
+                        Color32::GRAY
+                    },
+                );
+            }
+
+            if let Some(event) = lifestyle.current() {
+                ui.painter().text(
+                    egui::pos2(
+                        egui::lerp(rect.left()..=rect.right(), 0.75),
+                        rect.center().y,
+                    ),
+                    Align2::CENTER_CENTER,
+                    &format!("{}", event),
+                    TextStyle::Small,
+                    Color32::LIGHT_YELLOW,
+                );
+            }
+
+            ui.painter().text(
+                egui::pos2(egui::lerp(rect.left()..=rect.right(), 0.9), rect.center().y),
+                Align2::CENTER_CENTER,
+                &format!("{}", state.stage),
+                TextStyle::Small,
+                Color32::WHITE,
+            );
+        }
+
+        rect
+    });
 }
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{immune_system::ImmuneSystem, pathogens::Pathogen, white_cells::WhiteCell, HostState};
+
+pub const FLOW_SPEED: f32 = 120.0;
+pub const MAX_SEGMENTS: usize = 16;
+// cancer grows in tissue, away from the blood stream
+pub const VESSEL_CLEARANCE: f32 = 200.0;
+const FLOW_STRENGTH: f32 = 1.5;
+const TRUNK_SEGMENTS: usize = 6;
+const BRANCHES: usize = 4;
+const BRANCH_LENGTH: usize = 2;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Segment {
+    pub start: Vec2,
+    pub end: Vec2,
+    // relative to the trunk, scaled by dilatation
+    pub width: f32,
+    // distance travelled by the blood before entering this segment
+    pub offset: f32,
+}
+
+pub struct Nearest {
+    pub point: Vec2,
+    pub direction: Vec2,
+    pub distance: f32,
+    pub width: f32,
+}
+
+impl Nearest {
+    pub fn inside(&self) -> bool {
+        self.distance < self.width
+    }
+
+    pub fn outside_by(&self) -> f32 {
+        (self.distance - self.width).max(0.0)
+    }
+}
+
+pub struct Vessels {
+    pub segments: Vec<Segment>,
+}
+
+impl Vessels {
+    pub fn generate(seed: u64, (width, height): (f32, f32)) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut segments = Vec::with_capacity(MAX_SEGMENTS);
+
+        // the trunk crosses the arena through the origin, where the immune system starts
+        let step = width / TRUNK_SEGMENTS as f32;
+        let points = (0..=TRUNK_SEGMENTS)
+            .map(|i| {
+                let y = if i == TRUNK_SEGMENTS / 2 {
+                    0.0
+                } else {
+                    rng.gen_range((-height * 0.12)..(height * 0.12))
+                };
+                Vec2::new(-width / 2.0 + i as f32 * step, y)
+            })
+            .collect::<Vec<_>>();
+        let mut offset = 0.0;
+        for pair in points.windows(2) {
+            segments.push(Segment {
+                start: pair[0],
+                end: pair[1],
+                width: 1.0,
+                offset,
+            });
+            offset += pair[0].distance(pair[1]);
+        }
+
+        for _ in 0..BRANCHES {
+            let trunk = segments[rng.gen_range(0..TRUNK_SEGMENTS)];
+            let along = rng.gen_range(0.2..0.8);
+            let mut start = trunk.start.lerp(trunk.end, along);
+            let mut offset = trunk.offset + trunk.start.distance(start);
+            let side = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+            let mut angle: f32 = side * rng.gen_range(0.5..1.2);
+            let mut caliber = 0.6;
+            for _ in 0..BRANCH_LENGTH {
+                let length = rng.gen_range((height * 0.15)..(height * 0.25));
+                let end = (start + Vec2::new(angle.cos(), angle.sin()) * length).clamp(
+                    Vec2::new(-width / 2.0, -height / 2.0),
+                    Vec2::new(width / 2.0, height / 2.0),
+                );
+                segments.push(Segment {
+                    start,
+                    end,
+                    width: caliber,
+                    offset,
+                });
+                offset += start.distance(end);
+                start = end;
+                angle += rng.gen_range(-0.4..0.4);
+                caliber *= 0.7;
+            }
+        }
+
+        Vessels { segments }
+    }
+
+    pub fn nearest(&self, position: Vec2, dilatation: f32) -> Nearest {
+        self.segments
+            .iter()
+            .map(|segment| {
+                let along = segment.end - segment.start;
+                let t = ((position - segment.start).dot(along) / along.length_squared())
+                    .clamp(0.0, 1.0);
+                let point = segment.start + along * t;
+                Nearest {
+                    point,
+                    direction: along.normalize_or_zero(),
+                    distance: point.distance(position),
+                    width: segment.width * dilatation / 5.0,
+                }
+            })
+            .min_by(|a, b| {
+                (a.distance / a.width)
+                    .partial_cmp(&(b.distance / b.width))
+                    .unwrap()
+            })
+            .unwrap()
+    }
+
+    // blood runs fastest in the middle of the vessel and stops at its walls
+    pub fn current(&self, position: Vec2, dilatation: f32) -> Vec2 {
+        let nearest = self.nearest(position, dilatation);
+        if nearest.inside() {
+            nearest.direction * FLOW_SPEED * (1.0 - (nearest.distance / nearest.width).powi(2))
+        } else {
+            Vec2::ZERO
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn flow(
+    time: Res<Time>,
+    host_state: Res<HostState>,
+    vessels: Res<Vessels>,
+    mut bodies: Query<
+        (&RigidBodyPositionComponent, &mut RigidBodyVelocityComponent),
+        Or<(With<ImmuneSystem>, With<WhiteCell>, With<Pathogen>)>,
+    >,
+) {
+    for (rb_position, mut rb_velocity) in bodies.iter_mut() {
+        let current = vessels.current(
+            rb_position.position.translation.into(),
+            host_state.dilatation,
+        );
+        if current != Vec2::ZERO {
+            let velocity: Vec2 = rb_velocity.linvel.into();
+            rb_velocity.linvel = (velocity + current * FLOW_STRENGTH * time.delta_seconds()).into();
+        }
+    }
+}
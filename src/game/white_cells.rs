@@ -3,14 +3,88 @@ use bevy_rapier2d::prelude::{
     IntersectionEvent, IntoEntity, RigidBodyForcesComponent, RigidBodyPositionComponent,
 };
 
-use crate::assets::AudioAssets;
+use crate::assets::InGameAssets;
 
-use super::{immune_system::ImmuneSystem, pathogens::Pathogen, HostState};
+use super::{
+    immune_system::ImmuneSystem,
+    pathogens::{Cancer, Pathogen, PathogenKind},
+    HostState,
+};
+
+const MACROPHAGE_CAPACITY: usize = 3;
+const HUNTING_RANGE: f32 = 300.0;
+
+// cells from a lymph node guard it instead of following the immune system
+#[derive(Component)]
+pub struct Anchor(pub Vec2);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WhiteCellKind {
+    Neutrophil,
+    Macrophage,
+    KillerT,
+    BCell,
+}
+
+impl WhiteCellKind {
+    pub const UNLOCK_ORDER: [WhiteCellKind; 3] = [
+        WhiteCellKind::Macrophage,
+        WhiteCellKind::KillerT,
+        WhiteCellKind::BCell,
+    ];
+
+    pub const fn weight(self) -> u32 {
+        match self {
+            WhiteCellKind::Neutrophil => 6,
+            WhiteCellKind::Macrophage => 2,
+            WhiteCellKind::KillerT => 2,
+            WhiteCellKind::BCell => 1,
+        }
+    }
+
+    pub const fn radius(self) -> f32 {
+        match self {
+            WhiteCellKind::Macrophage => 4.0,
+            _ => 2.0,
+        }
+    }
+
+    const fn orbit(self) -> f32 {
+        match self {
+            WhiteCellKind::Neutrophil => 12.5,
+            WhiteCellKind::Macrophage => 25.0,
+            WhiteCellKind::KillerT => 12.5,
+            WhiteCellKind::BCell => 35.0,
+        }
+    }
+
+    pub fn texture(self, assets: &InGameAssets) -> Handle<Image> {
+        match self {
+            WhiteCellKind::Neutrophil => assets.white_cell.clone_weak(),
+            WhiteCellKind::Macrophage => assets.macrophage.clone_weak(),
+            WhiteCellKind::KillerT => assets.killer_t.clone_weak(),
+            WhiteCellKind::BCell => assets.b_cell.clone_weak(),
+        }
+    }
+}
 
 #[derive(Component)]
 pub struct WhiteCell {
+    pub kind: WhiteCellKind,
     pub spawned_at: f32,
-    pub strength: f32,
+    pub damage: f32,
+    engulfed: usize,
+}
+
+impl WhiteCell {
+    pub fn new(kind: WhiteCellKind, spawned_at: f32, damage: f32) -> WhiteCell {
+        WhiteCell {
+            kind,
+            spawned_at,
+            damage,
+            engulfed: 0,
+        }
+    }
 }
 
 pub fn movements(
@@ -19,17 +93,33 @@ pub fn movements(
         &RigidBodyPositionComponent,
         &mut RigidBodyForcesComponent,
         &WhiteCell,
+        Option<&Anchor>,
     )>,
+    cancer_cells: Query<&Transform, With<Cancer>>,
     time: Res<Time>,
 ) {
     let target = immune_system.single().translation.truncate();
-    for (rb_position, mut rb_forces, white_cell) in pathogens.iter_mut() {
+    let prey = cancer_cells
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .filter(|position| position.distance_squared(target) < HUNTING_RANGE.powi(2))
+        .min_by(|a, b| {
+            a.distance_squared(target)
+                .partial_cmp(&b.distance_squared(target))
+                .unwrap()
+        });
+    for (rb_position, mut rb_forces, white_cell, anchor) in pathogens.iter_mut() {
         let position: Vec2 = rb_position.position.translation.into();
-        let target = target
-            + Vec2::new(
-                (time.seconds_since_startup() as f32 * 5.0 - white_cell.spawned_at).sin(),
-                (time.seconds_since_startup() as f32 * 5.0 - white_cell.spawned_at).cos(),
-            ) * 12.5;
+        let target = match (white_cell.kind, prey, anchor) {
+            (WhiteCellKind::KillerT, Some(prey), None) => prey,
+            (_, _, anchor) => {
+                anchor.map_or(target, |Anchor(node)| *node)
+                    + Vec2::new(
+                        (time.seconds_since_startup() as f32 * 5.0 - white_cell.spawned_at).sin(),
+                        (time.seconds_since_startup() as f32 * 5.0 - white_cell.spawned_at).cos(),
+                    ) * white_cell.kind.orbit()
+            }
+        };
         let order = target - position;
         if order.length_squared() < 150.0 {
             let move_by = order.clamp_length_max(2.0) * 10000.0;
@@ -44,53 +134,68 @@ pub fn movements(
 pub fn attack(
     mut commands: Commands,
     mut intersection_events: EventReader<IntersectionEvent>,
-    white_cells: Query<&WhiteCell>,
-    pathogens: Query<&Pathogen>,
-    mut host_state: ResMut<HostState>,
-    audio_assets: Res<AudioAssets>,
-    audio: Res<Audio>,
+    mut white_cells: Query<&mut WhiteCell>,
+    mut pathogens: Query<&mut Pathogen>,
+    host_state: Res<HostState>,
 ) {
-    let mut hit = vec![];
-    let mut destroyed = 0;
+    let mut used = vec![];
     for event in intersection_events.iter() {
         if event.intersecting {
             let e1 = event.collider1.entity();
             let e2 = event.collider2.entity();
-            let (white_cell, pathogen) = if let Ok(white_cell) = white_cells.get(e1) {
-                if let Ok(pathogen) = pathogens.get(e2) {
-                    ((e1, white_cell), (e2, pathogen))
+            let (white_cell, pathogen) = if white_cells.get(e1).is_ok() {
+                if pathogens.get(e2).is_ok() {
+                    (e1, e2)
                 } else {
                     continue;
                 }
-            } else if let Ok(white_cell) = white_cells.get(e2) {
-                if let Ok(pathogen) = pathogens.get(e1) {
-                    ((e2, white_cell), (e1, pathogen))
+            } else if white_cells.get(e2).is_ok() {
+                if pathogens.get(e1).is_ok() {
+                    (e2, e1)
                 } else {
                     continue;
                 }
             } else {
                 continue;
             };
-            if hit.contains(&pathogen.0) {
+            if used.contains(&white_cell) {
                 continue;
             }
-            hit.push(pathogen.0);
-            if white_cell.1.strength > pathogen.1.strength {
-                commands.entity(white_cell.0).despawn_recursive();
-                commands.entity(pathogen.0).despawn_recursive();
-                destroyed += 1;
-                audio.play(
-                    audio_assets.pathogen_destroyed.clone_weak(),
-                    PlaybackSettings {
-                        repeat: false,
-                        speed: 1.0,
-                        volume: 0.2,
-                    },
-                );
-            } else {
-                commands.entity(white_cell.0).despawn_recursive();
+            let mut cell = white_cells.get_mut(white_cell).unwrap();
+            // B-cells keep their distance and fight with antibodies
+            if cell.kind == WhiteCellKind::BCell {
+                continue;
+            }
+            let mut pathogen = pathogens.get_mut(pathogen).unwrap();
+            if pathogen.is_dead() {
+                continue;
+            }
+            let damage = host_state.memory.damage(pathogen.kind, cell.damage);
+            match (cell.kind, pathogen.kind) {
+                (WhiteCellKind::Macrophage, PathogenKind::Bacteria | PathogenKind::Virus) => {
+                    let health = pathogen.health;
+                    pathogen.pierce(health);
+                }
+                // killer T cells recognise cancer directly, they're the answer to
+                // small tumours hiding behind their evasive armor
+                (WhiteCellKind::KillerT, PathogenKind::Cancer) => {
+                    pathogen.pierce(damage * 2.0);
+                }
+                _ => pathogen.hit(damage),
+            }
+            // macrophages fill up on what they kill, and wear down on what they can't engulf
+            if cell.kind == WhiteCellKind::Macrophage {
+                if pathogen.is_dead()
+                    || !matches!(pathogen.kind, PathogenKind::Bacteria | PathogenKind::Virus)
+                {
+                    cell.engulfed += 1;
+                }
+                if cell.engulfed < MACROPHAGE_CAPACITY {
+                    continue;
+                }
             }
+            used.push(white_cell);
+            commands.entity(white_cell).despawn_recursive();
         }
     }
-    host_state.exp += destroyed;
 }
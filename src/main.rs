@@ -14,6 +14,9 @@ use bevy_easings::EasingsPlugin;
 use bevy_egui::EguiPlugin;
 use bevy_rapier2d::physics::{NoUserData, RapierConfiguration, RapierPhysicsPlugin};
 
+use game::{genome::Genome, memory::ImmuneMemory};
+use progress::Strains;
+
 mod assets;
 mod bloodfield;
 mod camera;
@@ -96,6 +99,11 @@ fn main() {
             sick_days: usize::MAX,
             free_healthcare: usize::MAX,
             parental_leave: usize::MAX,
+            phage_therapy: usize::MAX,
+            booster_shots: usize::MAX,
+            resistance: Strains::default(),
+            memory: ImmuneMemory::default(),
+            genome: Genome::random(),
         })
         .insert_resource(UxState {
             background_loop: None,
@@ -123,6 +131,7 @@ pub enum GameState {
     LevelUp,
     Dead,
     Oldest,
+    Milestone,
     Exit,
     Intro,
 }
@@ -152,6 +161,11 @@ pub struct GlobalState {
     pub sick_days: usize,
     pub free_healthcare: usize,
     pub parental_leave: usize,
+    pub phage_therapy: usize,
+    pub booster_shots: usize,
+    pub resistance: Strains,
+    pub memory: ImmuneMemory,
+    pub genome: Genome,
 }
 
 impl GlobalState {
@@ -184,5 +198,15 @@ impl GlobalState {
             } else {
                 0
             })
+            + (if self.phage_therapy != usize::MAX {
+                1
+            } else {
+                0
+            })
+            + (if self.booster_shots != usize::MAX {
+                1
+            } else {
+                0
+            })
     }
 }
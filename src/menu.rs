@@ -125,6 +125,14 @@ fn setup(
                 Progress::ParentalLeave.to_image_id(),
                 assets.parental_leave.clone_weak(),
             );
+            egui_context.set_egui_texture(
+                Progress::PhageTherapy.to_image_id(),
+                assets.phage_therapy.clone_weak(),
+            );
+            egui_context.set_egui_texture(
+                Progress::BoosterShots.to_image_id(),
+                assets.booster_shots.clone_weak(),
+            );
             ux.progress_loaded = true;
         }
     }
@@ -205,6 +213,19 @@ fn menu(
                             TextFormat::simple(egui::TextStyle::Small, Color32::GRAY),
                         );
                     }
+                    new_game.append(
+                        &format!(
+                            "\n{}",
+                            global_state
+                                .genome
+                                .genes()
+                                .map(|gene| gene.to_string())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                        0.0,
+                        TextFormat::simple(egui::TextStyle::Small, Color32::LIGHT_GRAY),
+                    );
 
                     button(
                         ui,
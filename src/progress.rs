@@ -3,15 +3,23 @@ use std::fmt::{self, Formatter};
 use bevy::prelude::*;
 use bevy_egui::{
     egui::{
-        self, text::LayoutJob, Align2, Color32, ImageButton, Layout, RichText, TextFormat, Ui,
-        Widget, WidgetText,
+        self, text::LayoutJob, Align2, Color32, Rect, RichText, Sense, Stroke, TextFormat,
+        TextStyle, Ui, WidgetText,
     },
     EguiContext,
 };
-use strum::EnumIter;
+use strum::{EnumIter, IntoEnumIterator};
 
 use crate::{assets::AudioAssets, menu::button, GameState, GlobalState};
 
+const TIER_GATE: usize = 2;
+const NODE_SIZE: f32 = 48.0;
+const TIER_SPACING: f32 = 190.0;
+const ROW_SPACING: f32 = 90.0;
+const ZOOM_SPEED: f32 = 0.002;
+const MIN_ZOOM: f32 = 0.5;
+const MAX_ZOOM: f32 = 2.0;
+
 pub struct ProgressPlugin;
 
 impl Plugin for ProgressPlugin {
@@ -20,7 +28,7 @@ impl Plugin for ProgressPlugin {
     }
 }
 
-#[derive(EnumIter)]
+#[derive(EnumIter, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Progress {
     Disinfectant,
     Antibiotics,
@@ -31,6 +39,8 @@ pub enum Progress {
     SickDays,
     FreeHealthcare,
     ParentalLeave,
+    PhageTherapy,
+    BoosterShots,
 }
 
 impl fmt::Display for Progress {
@@ -45,6 +55,8 @@ impl fmt::Display for Progress {
             Progress::SickDays => f.pad("Sick Days"),
             Progress::FreeHealthcare => f.pad("Free Healthcare"),
             Progress::ParentalLeave => f.pad("Parental Leave"),
+            Progress::PhageTherapy => f.pad("Phage Therapy"),
+            Progress::BoosterShots => f.pad("Booster Shots"),
         }
     }
 }
@@ -61,6 +73,8 @@ impl Progress {
             Progress::SickDays => 6,
             Progress::FreeHealthcare => 7,
             Progress::ParentalLeave => 8,
+            Progress::PhageTherapy => 15,
+            Progress::BoosterShots => 16,
         }
     }
 
@@ -88,7 +102,7 @@ impl Progress {
             }
             Progress::Vaccine => {
                 layout.append(
-                    "Large virus risk reduction",
+                    "Large virus risk reduction and immune memory against viruses",
                     0.0,
                     TextFormat::simple(egui::TextStyle::Small, Color32::LIGHT_GRAY),
                 );
@@ -220,12 +234,71 @@ impl Progress {
                     TextFormat::simple(egui::TextStyle::Small, Color32::LIGHT_GRAY),
                 );
             }
+            Progress::PhageTherapy => {
+                layout.append(
+                    "Counter resistant bacteria",
+                    0.0,
+                    TextFormat::simple(egui::TextStyle::Small, Color32::LIGHT_GRAY),
+                );
+            }
+            Progress::BoosterShots => {
+                layout.append(
+                    "Counter resistant virus",
+                    0.0,
+                    TextFormat::simple(egui::TextStyle::Small, Color32::LIGHT_GRAY),
+                );
+            }
         }
         layout.into()
     }
 }
 
 impl Progress {
+    const fn tier(&self) -> usize {
+        match self {
+            Progress::Disinfectant | Progress::Antibiotics => 0,
+            Progress::PersonalHygiene | Progress::Sanitation | Progress::Vaccine => 1,
+            Progress::SickDays | Progress::FreeHealthcare | Progress::PreventiveMeasures => 2,
+            Progress::ParentalLeave | Progress::PhageTherapy | Progress::BoosterShots => 3,
+        }
+    }
+
+    const fn row(&self) -> f32 {
+        match self {
+            Progress::PersonalHygiene | Progress::SickDays | Progress::ParentalLeave => 0.0,
+            Progress::Disinfectant => 0.5,
+            Progress::Sanitation => 1.0,
+            Progress::FreeHealthcare => 1.5,
+            Progress::PhageTherapy => 2.0,
+            Progress::Antibiotics => 2.5,
+            Progress::Vaccine | Progress::PreventiveMeasures => 3.0,
+            Progress::BoosterShots => 3.5,
+        }
+    }
+
+    const fn prerequisites(&self) -> &'static [Progress] {
+        match self {
+            Progress::Disinfectant | Progress::Antibiotics => &[],
+            Progress::PersonalHygiene | Progress::Sanitation => &[Progress::Disinfectant],
+            Progress::Vaccine | Progress::PhageTherapy => &[Progress::Antibiotics],
+            Progress::SickDays => &[Progress::PersonalHygiene],
+            Progress::FreeHealthcare => &[Progress::Sanitation, Progress::Antibiotics],
+            Progress::PreventiveMeasures | Progress::BoosterShots => &[Progress::Vaccine],
+            Progress::ParentalLeave => &[Progress::SickDays],
+        }
+    }
+
+    // branches that close each other off
+    const fn excludes(&self) -> Option<Progress> {
+        match self {
+            Progress::FreeHealthcare => Some(Progress::ParentalLeave),
+            Progress::ParentalLeave => Some(Progress::FreeHealthcare),
+            Progress::PhageTherapy => Some(Progress::BoosterShots),
+            Progress::BoosterShots => Some(Progress::PhageTherapy),
+            _ => None,
+        }
+    }
+
     fn base_and_factor(&self) -> (usize, usize) {
         match self {
             Progress::Disinfectant => (25, 1),
@@ -237,6 +310,8 @@ impl Progress {
             Progress::SickDays => (100, 5),
             Progress::FreeHealthcare => (100, 10),
             Progress::ParentalLeave => (100, 20),
+            Progress::PhageTherapy => (100, 10),
+            Progress::BoosterShots => (100, 10),
         }
     }
 }
@@ -301,10 +376,35 @@ impl Effect {
                 self.cancer -= 0.04;
                 self.regen += 0.1;
             }
+            // they work on resistant strains, see `GlobalState::effect`
+            Progress::PhageTherapy => {}
+            Progress::BoosterShots => {}
         }
     }
 }
 
+const MAX_RESISTANCE: f32 = 0.75;
+
+#[derive(Default, Clone, Copy)]
+pub struct Strains {
+    pub bacteria: f32,
+    pub virus: f32,
+}
+
+impl Strains {
+    pub fn evolve(&mut self, exposure: &Strains) {
+        fn evolve(resistance: f32, exposure: f32) -> f32 {
+            if exposure > 0.0 {
+                (resistance + exposure / 2000.0).min(MAX_RESISTANCE)
+            } else {
+                resistance * 0.8
+            }
+        }
+        self.bacteria = evolve(self.bacteria, exposure.bacteria);
+        self.virus = evolve(self.virus, exposure.virus);
+    }
+}
+
 impl GlobalState {
     pub fn has(&self, progress: &Progress) -> bool {
         match progress {
@@ -317,6 +417,47 @@ impl GlobalState {
             Progress::SickDays => self.sick_days != usize::MAX,
             Progress::FreeHealthcare => self.free_healthcare != usize::MAX,
             Progress::ParentalLeave => self.parental_leave != usize::MAX,
+            Progress::PhageTherapy => self.phage_therapy != usize::MAX,
+            Progress::BoosterShots => self.booster_shots != usize::MAX,
+        }
+    }
+
+    pub fn effect(&self) -> Effect {
+        let mut effect = Effect::default();
+        for progress in Progress::iter() {
+            if self.has(&progress) {
+                effect.apply(progress);
+            }
+        }
+        self.genome.apply(&mut effect);
+        // resistant strains shrug off part of the risk reduction
+        effect.bacteria *= 1.0 - self.bacteria_resistance();
+        effect.virus *= 1.0 - self.virus_resistance();
+        effect
+    }
+
+    pub fn bacteria_resistance(&self) -> f32 {
+        if self.has(&Progress::PhageTherapy) {
+            self.resistance.bacteria / 4.0
+        } else {
+            self.resistance.bacteria
+        }
+    }
+
+    pub fn virus_resistance(&self) -> f32 {
+        if self.has(&Progress::BoosterShots) {
+            self.resistance.virus / 4.0
+        } else {
+            self.resistance.virus
+        }
+    }
+
+    // counter-measures applying selection pressure on each strain
+    pub fn counter_measures(&self) -> Strains {
+        let owned = |progress| if self.has(&progress) { 1.0 } else { 0.0 };
+        Strains {
+            bacteria: owned(Progress::Disinfectant) + owned(Progress::Antibiotics),
+            virus: owned(Progress::Disinfectant) + owned(Progress::Vaccine),
         }
     }
 
@@ -331,12 +472,72 @@ impl GlobalState {
             Progress::SickDays => self.sick_days = self.generation,
             Progress::FreeHealthcare => self.free_healthcare = self.generation,
             Progress::ParentalLeave => self.parental_leave = self.generation,
+            Progress::PhageTherapy => self.phage_therapy = self.generation,
+            Progress::BoosterShots => self.booster_shots = self.generation,
         }
     }
 
     fn cost(&self, progress: &Progress) -> usize {
         progress.base_and_factor().0
-            + progress.base_and_factor().1 * (self.current_progress_multiplier() * 2 / 3 + 2)
+            + progress.base_and_factor().1
+                * (self.current_progress_multiplier() * 2 / 3 + 2 + progress.tier())
+    }
+
+    fn lock(&self, progress: &Progress) -> Option<Lock> {
+        if let Some(required) = progress
+            .prerequisites()
+            .iter()
+            .find(|required| !self.has(required))
+        {
+            return Some(Lock::Requires(*required));
+        }
+        if let Some(excluded) = progress.excludes().filter(|excluded| self.has(excluded)) {
+            return Some(Lock::Excludes(excluded));
+        }
+        if self.current_progress_multiplier() < progress.tier() * TIER_GATE {
+            return Some(Lock::Tier(progress.tier() * TIER_GATE));
+        }
+        let resistance = match progress {
+            Progress::PhageTherapy => Some(self.resistance.bacteria),
+            Progress::BoosterShots => Some(self.resistance.virus),
+            _ => None,
+        };
+        match resistance {
+            Some(resistance) if resistance <= 0.01 => Some(Lock::NoResistance),
+            _ => None,
+        }
+    }
+}
+
+enum Lock {
+    Requires(Progress),
+    Excludes(Progress),
+    Tier(usize),
+    NoResistance,
+}
+
+impl fmt::Display for Lock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Lock::Requires(progress) => write!(f, "Requires {}", progress),
+            Lock::Excludes(progress) => write!(f, "Excluded by {}", progress),
+            Lock::Tier(owned) => write!(f, "Requires {} upgrades", owned),
+            Lock::NoResistance => write!(f, "No resistant strains"),
+        }
+    }
+}
+
+struct View {
+    pan: egui::Vec2,
+    zoom: f32,
+}
+
+impl Default for View {
+    fn default() -> Self {
+        View {
+            pan: egui::Vec2::ZERO,
+            zoom: 1.0,
+        }
     }
 }
 
@@ -346,6 +547,7 @@ fn progress(
     mut global_state: ResMut<GlobalState>,
     audio_assets: Res<AudioAssets>,
     audio: Res<Audio>,
+    mut view: Local<View>,
 ) {
     egui::Window::new(RichText::new("Cleanse").color(Color32::RED))
         .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
@@ -358,79 +560,17 @@ fn progress(
                     "Progress Points: {:.0}",
                     global_state.progress.floor()
                 ));
+                let resistance = global_state.resistance;
+                if resistance.bacteria > 0.01 || resistance.virus > 0.01 {
+                    ui.small(format!(
+                        "Resistant strains - bacteria: {:.0}%, virus: {:.0}%",
+                        resistance.bacteria * 100.0,
+                        resistance.virus * 100.0
+                    ));
+                }
                 ui.separator();
 
-                ui.horizontal(|ui| {
-                    image_button(
-                        ui,
-                        Progress::Disinfectant,
-                        &mut *global_state,
-                        &audio_assets,
-                        &audio,
-                    );
-                    image_button(
-                        ui,
-                        Progress::Antibiotics,
-                        &mut *global_state,
-                        &audio_assets,
-                        &audio,
-                    );
-                    image_button(
-                        ui,
-                        Progress::Vaccine,
-                        &mut *global_state,
-                        &audio_assets,
-                        &audio,
-                    );
-                });
-
-                ui.horizontal(|ui| {
-                    image_button(
-                        ui,
-                        Progress::PersonalHygiene,
-                        &mut *global_state,
-                        &audio_assets,
-                        &audio,
-                    );
-                    image_button(
-                        ui,
-                        Progress::Sanitation,
-                        &mut *global_state,
-                        &audio_assets,
-                        &audio,
-                    );
-                    image_button(
-                        ui,
-                        Progress::PreventiveMeasures,
-                        &mut *global_state,
-                        &audio_assets,
-                        &audio,
-                    );
-                });
-
-                ui.horizontal(|ui| {
-                    image_button(
-                        ui,
-                        Progress::SickDays,
-                        &mut *global_state,
-                        &audio_assets,
-                        &audio,
-                    );
-                    image_button(
-                        ui,
-                        Progress::FreeHealthcare,
-                        &mut *global_state,
-                        &audio_assets,
-                        &audio,
-                    );
-                    image_button(
-                        ui,
-                        Progress::ParentalLeave,
-                        &mut *global_state,
-                        &audio_assets,
-                        &audio,
-                    );
-                });
+                tree(ui, &mut view, &mut *global_state, &audio_assets, &audio);
 
                 ui.add_space(20.0);
                 ui.vertical_centered(|ui| {
@@ -458,55 +598,130 @@ fn progress(
         });
 }
 
-fn image_button(
+fn tree(
     ui: &mut Ui,
-    progress: Progress,
+    view: &mut View,
     global_state: &mut GlobalState,
     audio_assets: &Res<AudioAssets>,
     audio: &Res<Audio>,
 ) {
-    ui.with_layout(Layout::left_to_right(), |ui| {
-        ui.set_width(300.0);
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(780.0, 420.0), Sense::drag());
+    view.pan += response.drag_delta();
+    if response.hovered() {
+        let scroll = ui.input().scroll_delta.y;
+        view.zoom = (view.zoom * (1.0 + scroll * ZOOM_SPEED)).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
 
-        let cost = global_state.cost(&progress);
+    let origin = rect.center() + view.pan;
+    let zoom = view.zoom;
+    let place = |progress: &Progress| {
+        origin
+            + egui::vec2(
+                (progress.tier() as f32 - 1.5) * TIER_SPACING,
+                (progress.row() - 1.75) * ROW_SPACING,
+            ) * zoom
+    };
 
-        if match (global_state.has(&progress), cost) {
-            (true, _) => true,
-            (_, x) if (x as f32) > global_state.progress => true,
-            _ => false,
-        } {
-            ui.set_enabled(false);
+    let painter = ui.painter_at(rect);
+    painter.rect_stroke(rect, 4.0, Stroke::new(1.0, Color32::DARK_GRAY));
+    for progress in Progress::iter() {
+        for required in progress.prerequisites() {
+            let color = if global_state.has(required) {
+                Color32::DARK_GREEN
+            } else {
+                Color32::GRAY
+            };
+            painter.line_segment(
+                [place(required), place(&progress)],
+                Stroke::new(2.0 * zoom, color),
+            );
         }
-
-        if ImageButton::new(
-            egui::TextureId::User(progress.to_image_id()),
-            egui::vec2(48.0, 48.0),
-        )
-        .tint(match (global_state.has(&progress), cost) {
-            (true, _) => Color32::DARK_GREEN,
-            (_, x) if (x as f32) > global_state.progress => Color32::DARK_GRAY,
-            _ => Color32::WHITE,
-        })
-        .ui(ui)
-        .on_hover_text(progress.details())
-        .on_disabled_hover_text(progress.details())
-        .clicked()
-        {
-            audio.play(
-                audio_assets.improved.clone_weak(),
-                PlaybackSettings {
-                    repeat: false,
-                    speed: 1.0,
-                    volume: 0.2,
-                },
+        if let Some(excluded) = progress.excludes() {
+            painter.line_segment(
+                [place(&excluded), place(&progress)],
+                Stroke::new(zoom, Color32::DARK_RED),
             );
-            global_state.progress -= cost as f32;
-            global_state.get(&progress);
         }
-        if global_state.has(&progress) {
-            ui.small(&format!("{:<20}\n\n", progress));
+    }
+
+    let mut canvas = ui.child_ui(rect, *ui.layout());
+    canvas.set_clip_rect(rect);
+    for progress in Progress::iter() {
+        node(
+            &mut canvas,
+            place(&progress),
+            zoom,
+            progress,
+            global_state,
+            audio_assets,
+            audio,
+        );
+    }
+}
+
+fn node(
+    ui: &mut Ui,
+    center: egui::Pos2,
+    zoom: f32,
+    progress: Progress,
+    global_state: &mut GlobalState,
+    audio_assets: &Res<AudioAssets>,
+    audio: &Res<Audio>,
+) {
+    let cost = global_state.cost(&progress);
+    let owned = global_state.has(&progress);
+    let lock = global_state.lock(&progress);
+    let affordable = (cost as f32) <= global_state.progress;
+    let available = !owned && lock.is_none() && affordable;
+
+    let rect = Rect::from_center_size(center, egui::Vec2::splat(NODE_SIZE * zoom));
+    let response = ui
+        .interact(rect, ui.id().with(progress.to_image_id()), Sense::click())
+        .on_hover_text(progress.details());
+
+    let tint = match (owned, &lock) {
+        (true, _) => Color32::DARK_GREEN,
+        (_, Some(_)) => Color32::from_gray(40),
+        _ if !affordable => Color32::DARK_GRAY,
+        _ => Color32::WHITE,
+    };
+    let painter = ui.painter();
+    painter.image(
+        egui::TextureId::User(progress.to_image_id()),
+        rect,
+        Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+        tint,
+    );
+    if available && response.hovered() {
+        painter.rect_stroke(rect.expand(2.0), 4.0, Stroke::new(1.0, Color32::WHITE));
+    }
+    let label = match (owned, &lock) {
+        (true, _) => format!("{}", progress),
+        (_, Some(lock)) => format!("{}\n{}", progress, lock),
+        _ => format!("{}\n{}", progress, cost),
+    };
+    painter.text(
+        rect.center_bottom() + egui::vec2(0.0, 4.0 * zoom),
+        Align2::CENTER_TOP,
+        &label,
+        TextStyle::Small,
+        if lock.is_some() && !owned {
+            Color32::GRAY
         } else {
-            ui.small(&format!("{:<20}\n\n{}", progress, cost));
-        }
-    });
+            Color32::WHITE
+        },
+    );
+
+    if available && response.clicked() {
+        audio.play(
+            audio_assets.improved.clone_weak(),
+            PlaybackSettings {
+                repeat: false,
+                speed: 1.0,
+                volume: 0.2,
+            },
+        );
+        global_state.progress -= cost as f32;
+        global_state.get(&progress);
+    }
 }